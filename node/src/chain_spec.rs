@@ -1,7 +1,9 @@
 use node_patternities_node_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig, ThePatternitiesConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
+	PatternitiesFeeDestination, Signature, SudoConfig, SystemConfig, ThePatternitiesConfig,
+	EXISTENTIAL_DEPOSIT, WASM_BINARY,
 };
+use patternities_primitives::showcase_key_material;
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{sr25519, Pair, Public};
@@ -132,17 +134,32 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+
 	GenesisConfig {
 		the_patternities: ThePatternitiesConfig {
-			the_patternities: vec![],
+			// Pre-mint a few showcase patterns for Alice and Bob.
+			the_patternities: vec![
+				(alice.clone(), None, showcase_key_material(2)),
+				(alice, Some(1 << 40), showcase_key_material(212)),
+				(bob.clone(), None, showcase_key_material(2242)),
+				(bob, Some(1 << 40), showcase_key_material(9922)),
+			],
 		},
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			// Configure endowed accounts with initial balance of 1 << 60, and keep the marketplace
+			// fee account alive so that fees below the existential deposit can be paid into it.
+			balances: endowed_accounts
+				.iter()
+				.cloned()
+				.map(|k| (k, 1 << 60))
+				.chain(Some((PatternitiesFeeDestination::get(), EXISTENTIAL_DEPOSIT)))
+				.collect(),
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
@@ -36,6 +36,15 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Render a pattern from the local database to a PNG or SVG file.
+	Render(crate::render::RenderCmd),
+
+	/// Generate the key material of a new pattern.
+	Keygen(crate::seal::KeygenCmd),
+
+	/// Seal the key of a pattern to an account.
+	Seal(crate::seal::SealCmd),
+
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[clap(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -98,6 +98,23 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::Render(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, keystore_container, .. } =
+					service::new_partial(&config)?;
+				cmd.run(client, keystore_container.local_keystore())
+			})
+		},
+		Some(Subcommand::Keygen(cmd)) => cmd.run(),
+		Some(Subcommand::Seal(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, keystore_container, .. } =
+					service::new_partial(&config)?;
+				cmd.run(client, keystore_container.local_keystore())
+			})
+		},
 		Some(Subcommand::Benchmark(cmd)) =>
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
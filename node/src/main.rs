@@ -6,7 +6,10 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod render;
+mod reveal;
 mod rpc;
+mod seal;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,201 @@
+//! The `render` subcommand: renders a pattern straight from the local database.
+
+use crate::reveal;
+use codec::{Decode, Encode};
+use node_patternities_node_runtime::{
+	opaque::Block,
+	pallet_the_patternities::{
+		Generator, KeyMaterial, Pallet, PatternSeed, ProgramKind, SealedKey,
+	},
+	AccountId, Hash, Runtime,
+};
+use patternities_render::{Format, RenderParams, CANVAS_SIZE};
+use sc_cli::{CliConfiguration, KeystoreParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sc_keystore::LocalKeystore;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hashing::{twox_128, twox_64},
+	sr25519,
+	storage::StorageKey,
+};
+use sp_runtime::generic::BlockId;
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// The `render` command used to render a pattern to a PNG or SVG file.
+#[derive(Debug, clap::Parser)]
+pub struct RenderCmd {
+	/// Hash of the pattern to render.
+	#[clap(long)]
+	pub pattern: Hash,
+
+	/// File to write the rendered pattern to.
+	#[clap(long, parse(from_os_str))]
+	pub out: PathBuf,
+
+	/// Format to render the pattern in: `png`, or `svg` for large prints.
+	#[clap(long, default_value = "png")]
+	pub format: Format,
+
+	/// Width and height of the rendered pattern, in pixels.
+	#[clap(long, default_value_t = CANVAS_SIZE)]
+	pub size: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl RenderCmd {
+	/// Run the render command. The owner's account key must be in `keystore` to unseal the
+	/// pattern's key.
+	pub fn run<C, BE>(
+		&self,
+		client: Arc<C>,
+		keystore: Option<Arc<LocalKeystore>>,
+	) -> sc_cli::Result<()>
+	where
+		C: StorageProvider<Block, BE> + HeaderBackend<Block>,
+		BE: Backend<Block>,
+	{
+		let best = client.info().best_hash;
+		if pending_reseal(&*client, best, &self.pattern)?.is_some() {
+			return Err("The key of this pattern has not been re-sealed to its owner yet".into())
+		}
+		let (pattern, (key, _)) = unseal_pattern_key(&*client, best, keystore, &self.pattern)?;
+
+		let generator: Generator<Runtime> =
+			read_map(&*client, best, b"Generators", &pattern.generator)?
+				.ok_or("Pattern was minted with an unregistered generator")?;
+		let unsalted: Option<()> = read_map(&*client, best, b"Unsalted", &self.pattern)?;
+		if unsalted.is_some() {
+			return Err("The seed of this pattern is drawn in the next block".into())
+		}
+		let salt: Option<[u8; 32]> = read_map(&*client, best, b"SeedSalts", &self.pattern)?;
+		let seed = Pallet::<Runtime>::seed_of(&key, salt.as_ref());
+
+		// Source programs are rendered natively, which covers the builtin program only.
+		let image = match generator.kind {
+			ProgramKind::Wasm if self.format == Format::Svg =>
+				return Err("WASM generators can only be rendered as PNG".into()),
+			ProgramKind::Wasm => {
+				let code = read_program(&*client, best, &generator.program)?
+					.ok_or("The generator program is missing")?;
+				patternities_render::wasm::render_png(&code, seed, self.size)
+			},
+			ProgramKind::Source if generator.program == Pallet::<Runtime>::builtin_program() =>
+				patternities_render::render_as(
+					seed,
+					self.format,
+					&RenderParams::with_canvas_size(self.size),
+				),
+			ProgramKind::Source =>
+				return Err("Pattern was minted with an unknown generator program".into()),
+		}
+		.map_err(|e| e.to_string())?;
+		fs::write(&self.out, image)?;
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for RenderCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
+
+/// Read a pattern and unseal its key with the key holder's account key in `keystore`: the owner,
+/// or the previous owner while the pattern is pending a reseal.
+pub(crate) fn unseal_pattern_key<C, BE>(
+	client: &C,
+	at: Hash,
+	keystore: Option<Arc<LocalKeystore>>,
+	pattern_id: &Hash,
+) -> sc_cli::Result<(PatternSeed<Runtime>, KeyMaterial)>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	let pattern: PatternSeed<Runtime> =
+		read_map(client, at, b"Patternity", pattern_id)?.ok_or("Pattern not found")?;
+	let holder = pending_reseal(client, at, pattern_id)?.unwrap_or_else(|| pattern.owner.clone());
+	let sealed: SealedKey = read_map(client, at, b"SealedKeys", pattern_id)?
+		.ok_or("The key of this pattern is not sealed to any account")?;
+
+	let keystore = keystore.ok_or("A local keystore is required to unseal the key")?;
+	let holder = sr25519::Public::from_raw(*holder.as_ref());
+	let (key, iv) = reveal::unseal_with_keystore(&keystore, &holder, &sealed)?;
+
+	if Pallet::<Runtime>::key_hash(&key, &iv) != pattern.key_hash {
+		return Err("Unsealed key does not match the pattern".into())
+	}
+
+	Ok((pattern, (key, iv)))
+}
+
+/// The previous owner who still has to re-seal the key of a pattern, if any.
+fn pending_reseal<C, BE>(
+	client: &C,
+	at: Hash,
+	pattern_id: &Hash,
+) -> sc_cli::Result<Option<AccountId>>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	read_map(client, at, b"PendingReseal", pattern_id)
+}
+
+/// Read an entry of a `Twox64Concat` storage map of the patternities pallet.
+fn read_map<C, BE, K, V>(client: &C, at: Hash, storage: &[u8], key: &K) -> sc_cli::Result<Option<V>>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+	K: Encode,
+	V: Decode,
+{
+	let encoded = key.encode();
+	read_storage(client, at, storage, &[&twox_64(&encoded), &encoded[..]].concat())
+}
+
+/// Read the code of a generator program, which is stored under its hash as is.
+fn read_program<C, BE>(client: &C, at: Hash, program: &Hash) -> sc_cli::Result<Option<Vec<u8>>>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	read_storage(client, at, b"GeneratorPrograms", program.as_ref())
+}
+
+fn read_storage<C, BE, V>(
+	client: &C,
+	at: Hash,
+	storage: &[u8],
+	hashed_key: &[u8],
+) -> sc_cli::Result<Option<V>>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+	V: Decode,
+{
+	let storage_key =
+		StorageKey([&twox_128(b"ThePatternities")[..], &twox_128(storage), hashed_key].concat());
+
+	client
+		.storage(&BlockId::Hash(at), &storage_key)
+		.map_err(|e| e.to_string())?
+		.map(|data| {
+			V::decode(&mut &data.0[..])
+				.map_err(|e| format!("Failed to decode pattern storage: {}", e).into())
+		})
+		.transpose()
+}
@@ -0,0 +1,109 @@
+//! Off-chain sealing of pattern keys.
+//!
+//! The runtime only stores a pattern's key and iv sealed to its owner. A key is sealed with
+//! schnorrkel's AEAD construction: an ephemeral key exchange against the owner's sr25519 public
+//! key yields an XChaCha20-Poly1305 cipher, so only the owner's account key can unseal it again.
+
+use chacha20poly1305::{
+	aead::{Aead, Payload},
+	XChaCha20Poly1305, XNonce,
+};
+use node_patternities_node_runtime::pallet_the_patternities::{SealedKey, SEALED_KEY_LEN};
+use rand::{rngs::OsRng, RngCore};
+use sc_keystore::LocalKeystore;
+use schnorrkel::{PublicKey, SecretKey};
+use sp_core::{
+	crypto::{KeyTypeId, Pair as _},
+	sr25519,
+};
+
+/// Key type under which owners insert their account key into the node's keystore.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"patt");
+
+mod app {
+	use sp_application_crypto::{app_crypto, sr25519};
+	app_crypto!(sr25519, super::KEY_TYPE);
+}
+
+/// Context of the key exchange, binding sealed keys to this use.
+const SEAL_CONTEXT: &[u8] = b"patternities/seal";
+
+const EPHEMERAL_LEN: usize = 32;
+const PAYLOAD_LEN: usize = 16 + 8;
+
+// Every seal derives its cipher from a fresh ephemeral key, so a cipher only ever encrypts one
+// message and a fixed nonce is safe.
+const NONCE: [u8; 24] = [0; 24];
+
+/// Generate fresh key material for a new pattern.
+pub fn generate_key_material() -> ([u8; 16], [u8; 8]) {
+	let mut key = [0u8; 16];
+	let mut iv = [0u8; 8];
+	OsRng.fill_bytes(&mut key);
+	OsRng.fill_bytes(&mut iv);
+	(key, iv)
+}
+
+/// Seal `key` and `iv` to the owner of `recipient`.
+pub fn seal(
+	recipient: &sr25519::Public,
+	key: &[u8; 16],
+	iv: &[u8; 8],
+) -> Result<SealedKey, String> {
+	let recipient = PublicKey::from_bytes(&recipient.0)
+		.map_err(|_| "Recipient is not a valid sr25519 public key".to_string())?;
+
+	let (ephemeral_public, cipher) =
+		recipient.init_aead_unauthenticated::<XChaCha20Poly1305>(SEAL_CONTEXT);
+	let ephemeral_public = ephemeral_public.to_bytes();
+
+	let mut payload = [0u8; PAYLOAD_LEN];
+	payload[..16].copy_from_slice(key);
+	payload[16..].copy_from_slice(iv);
+	let ciphertext = cipher
+		.encrypt(XNonce::from_slice(&NONCE), Payload { msg: &payload, aad: &ephemeral_public })
+		.map_err(|_| "Failed to seal the key".to_string())?;
+
+	let mut sealed = [0u8; SEALED_KEY_LEN];
+	sealed[..EPHEMERAL_LEN].copy_from_slice(&ephemeral_public);
+	sealed[EPHEMERAL_LEN..].copy_from_slice(&ciphertext);
+
+	Ok(sealed)
+}
+
+/// Unseal a key sealed to the account of `pair`.
+pub fn unseal(pair: &sr25519::Pair, sealed: &SealedKey) -> Result<([u8; 16], [u8; 8]), String> {
+	let keypair = SecretKey::from_bytes(&pair.to_raw_vec())
+		.map_err(|_| "Account key is not a valid sr25519 secret".to_string())?
+		.to_keypair();
+
+	let (ephemeral_public, ciphertext) = sealed.split_at(EPHEMERAL_LEN);
+	let ephemeral = PublicKey::from_bytes(ephemeral_public)
+		.map_err(|_| "Sealed key is malformed".to_string())?;
+
+	let cipher = keypair.aead_unauthenticated::<XChaCha20Poly1305>(SEAL_CONTEXT, &ephemeral);
+	let payload = cipher
+		.decrypt(XNonce::from_slice(&NONCE), Payload { msg: ciphertext, aad: ephemeral_public })
+		.map_err(|_| "Sealed key was not sealed to this account".to_string())?;
+
+	let mut key = [0u8; 16];
+	let mut iv = [0u8; 8];
+	key.copy_from_slice(&payload[..16]);
+	iv.copy_from_slice(&payload[16..]);
+
+	Ok((key, iv))
+}
+
+/// Unseal a key with the owner's account key, looked up in `keystore` under [`KEY_TYPE`].
+pub fn unseal_with_keystore(
+	keystore: &LocalKeystore,
+	owner: &sr25519::Public,
+	sealed: &SealedKey,
+) -> Result<([u8; 16], [u8; 8]), String> {
+	let pair = keystore
+		.key_pair::<app::Pair>(&app::Public::from(owner.clone()))
+		.map_err(|e| format!("Failed to read keystore: {}", e))?
+		.ok_or_else(|| "Owner key not found in keystore".to_string())?;
+
+	unseal(pair.as_ref(), sealed)
+}
@@ -0,0 +1,122 @@
+//! The `keygen` and `seal` subcommands: produce the key material and sealed keys that minting
+//! and handing over a pattern take.
+
+use crate::{render, reveal};
+use node_patternities_node_runtime::{
+	opaque::Block, pallet_the_patternities::Pallet, AccountId, Hash, Runtime,
+};
+use sc_cli::{CliConfiguration, KeystoreParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sc_keystore::LocalKeystore;
+use sp_blockchain::HeaderBackend;
+use sp_core::{bytes, sr25519};
+use std::sync::Arc;
+
+/// The `keygen` command used to generate the key material of a new pattern.
+#[derive(Debug, clap::Parser)]
+pub struct KeygenCmd {
+	/// Account to seal the key to, usually the minter. Prints the `sealed_key` for
+	/// `create_pattern` when given.
+	#[clap(long)]
+	pub owner: Option<AccountId>,
+}
+
+impl KeygenCmd {
+	/// Run the keygen command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let (key, iv) = reveal::generate_key_material();
+
+		println!("key:        {}", bytes::to_hex(&key, false));
+		println!("iv:         {}", bytes::to_hex(&iv, false));
+		println!("key_hash:   {}", bytes::to_hex(&Pallet::<Runtime>::key_hash(&key, &iv), false));
+
+		if let Some(owner) = &self.owner {
+			let sealed = reveal::seal(&sr25519::Public::from_raw(*owner.as_ref()), &key, &iv)?;
+			println!("sealed_key: {}", bytes::to_hex(&sealed, false));
+		}
+
+		Ok(())
+	}
+}
+
+/// The `seal` command used to seal the key of a pattern to an account, for `transfer`,
+/// `accept_offer` and `reseal`.
+#[derive(Debug, clap::Parser)]
+pub struct SealCmd {
+	/// Account to seal the key to.
+	#[clap(long)]
+	pub to: AccountId,
+
+	/// Pattern whose key to seal. The account key of its owner, or of the previous owner while
+	/// it is pending a reseal, must be in the keystore to unseal it.
+	#[clap(long, conflicts_with_all = &["key", "iv"], required_unless_present = "key")]
+	pub pattern: Option<Hash>,
+
+	/// Key to seal, in hex.
+	#[clap(long, requires = "iv", parse(try_from_str = parse_key))]
+	pub key: Option<[u8; 16]>,
+
+	/// Iv to seal, in hex.
+	#[clap(long, requires = "key", parse(try_from_str = parse_iv))]
+	pub iv: Option<[u8; 8]>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl SealCmd {
+	/// Run the seal command. With `--pattern`, the key is unsealed from the local database.
+	pub fn run<C, BE>(
+		&self,
+		client: Arc<C>,
+		keystore: Option<Arc<LocalKeystore>>,
+	) -> sc_cli::Result<()>
+	where
+		C: StorageProvider<Block, BE> + HeaderBackend<Block>,
+		BE: Backend<Block>,
+	{
+		let (key, iv) = match (&self.pattern, self.key, self.iv) {
+			(Some(pattern_id), _, _) => {
+				let best = client.info().best_hash;
+				render::unseal_pattern_key(&*client, best, keystore, pattern_id)?.1
+			},
+			(None, Some(key), Some(iv)) => (key, iv),
+			_ => return Err("Either --pattern or --key and --iv are required".into()),
+		};
+
+		let sealed = reveal::seal(&sr25519::Public::from_raw(*self.to.as_ref()), &key, &iv)?;
+		println!("{}", bytes::to_hex(&sealed, false));
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for SealCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
+
+fn parse_key(s: &str) -> Result<[u8; 16], String> {
+	parse_hex(s)
+}
+
+fn parse_iv(s: &str) -> Result<[u8; 8], String> {
+	parse_hex(s)
+}
+
+fn parse_hex<const N: usize>(s: &str) -> Result<[u8; N], String> {
+	bytes::from_hex(s)
+		.map_err(|e| e.to_string())?
+		.try_into()
+		.map_err(|_| format!("Expected {} bytes of hex", N))
+}
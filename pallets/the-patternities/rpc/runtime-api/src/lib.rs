@@ -0,0 +1,42 @@
+//! Runtime API definition for the patternities pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_the_patternities::{CollectionId, KeyStatus, PatternInfo, PatternTraits};
+
+sp_api::decl_runtime_apis! {
+	pub trait PatternitiesApi<AccountId, Balance, Hash> where
+		AccountId: Codec,
+		Balance: Codec,
+		Hash: Codec,
+	{
+		/// The pattern with the given hash, if any.
+		fn pattern(pattern_id: Hash) -> Option<PatternInfo<AccountId, Balance, Hash>>;
+		/// The owner of a pattern.
+		fn pattern_owner(pattern_id: Hash) -> Option<AccountId>;
+		/// The asking price of a pattern, if it is listed for sale.
+		fn pattern_price(pattern_id: Hash) -> Option<Balance>;
+		/// Whether the owner of a pattern can unseal its key.
+		fn key_status(pattern_id: Hash) -> Option<KeyStatus<AccountId>>;
+		/// Up to `limit` patterns owned by `owner`, skipping the first `start`.
+		fn patterns_owned(owner: AccountId, start: u32, limit: u32) -> Vec<Hash>;
+		/// Up to `limit` patterns listed for sale with their price, skipping the first `start`.
+		fn patterns_for_sale(start: u32, limit: u32) -> Vec<(Hash, Balance)>;
+		/// The number of patterns in existence.
+		fn pattern_count() -> u64;
+		/// Up to `limit` patterns of a collection, skipping the first `start`.
+		fn patterns_in_collection(collection_id: CollectionId, start: u32, limit: u32) -> Vec<Hash>;
+		/// The visual traits of a pattern, if its owner disclosed them.
+		fn pattern_traits(pattern_id: Hash) -> Option<PatternTraits>;
+		/// The visual traits the generator program draws for `seed`.
+		fn traits_of_seed(seed: u64) -> PatternTraits;
+		/// The rarity score of a pattern relative to the live patterns with disclosed traits,
+		/// if its traits were disclosed. Higher is rarer.
+		fn rarity_score(pattern_id: Hash) -> Option<u64>;
+		/// The code of a registered generator program.
+		fn generator_program(program: Hash) -> Option<Vec<u8>>;
+	}
+}
@@ -0,0 +1,164 @@
+//! RPC interface for the patternities pallet, served under the `patternities_` namespace.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_the_patternities_rpc_runtime_api::{
+	CollectionId, PatternInfo, PatternitiesApi as PatternitiesRuntimeApi,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Largest page returned by the listing methods.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc]
+pub trait PatternitiesApi<BlockHash, AccountId, Balance, Hash> {
+	/// Get the pattern with the given hash.
+	#[rpc(name = "patternities_pattern")]
+	fn pattern(
+		&self,
+		pattern_id: Hash,
+		at: Option<BlockHash>,
+	) -> Result<Option<PatternInfo<AccountId, Balance, Hash>>>;
+
+	/// List the patterns owned by an account, `limit` at a time starting from `start`.
+	#[rpc(name = "patternities_patternsOwned")]
+	fn patterns_owned(
+		&self,
+		owner: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Hash>>;
+
+	/// List the patterns currently for sale with their price, `limit` at a time starting from
+	/// `start`.
+	#[rpc(name = "patternities_patternsForSale")]
+	fn patterns_for_sale(
+		&self,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(Hash, Balance)>>;
+
+	/// Get the total number of patterns.
+	#[rpc(name = "patternities_patternCount")]
+	fn pattern_count(&self, at: Option<BlockHash>) -> Result<u64>;
+
+	/// List the patterns of a collection, `limit` at a time starting from `start`.
+	#[rpc(name = "patternities_patternsInCollection")]
+	fn patterns_in_collection(
+		&self,
+		collection_id: CollectionId,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Hash>>;
+}
+
+/// Provides RPC methods to query patterns.
+pub struct Patternities<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Patternities<C, P> {
+	/// Create new `Patternities` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query patterns.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance, Hash>
+	PatternitiesApi<<Block as BlockT>::Hash, AccountId, Balance, Hash> for Patternities<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PatternitiesRuntimeApi<Block, AccountId, Balance, Hash>,
+	AccountId: Codec,
+	Balance: Codec,
+	Hash: Codec,
+{
+	fn pattern(
+		&self,
+		pattern_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PatternInfo<AccountId, Balance, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pattern(&at, pattern_id).map_err(runtime_error)
+	}
+
+	fn patterns_owned(
+		&self,
+		owner: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.patterns_owned(&at, owner, start, limit.min(MAX_PAGE_SIZE)).map_err(runtime_error)
+	}
+
+	fn patterns_for_sale(
+		&self,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(Hash, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.patterns_for_sale(&at, start, limit.min(MAX_PAGE_SIZE)).map_err(runtime_error)
+	}
+
+	fn pattern_count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u64> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pattern_count(&at).map_err(runtime_error)
+	}
+
+	fn patterns_in_collection(
+		&self,
+		collection_id: CollectionId,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.patterns_in_collection(&at, collection_id, start, limit.min(MAX_PAGE_SIZE))
+			.map_err(runtime_error)
+	}
+}
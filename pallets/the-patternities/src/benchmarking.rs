@@ -0,0 +1,538 @@
+//! Benchmarking setup for pallet-the-patternities
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ThePatternities;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	sp_runtime::{
+		traits::{Hash, One, Saturating},
+		PerThing, Permill,
+	},
+	traits::{Currency, Get, Hooks},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_std::{vec, vec::Vec};
+
+const KEY: [u8; 16] = [1; 16];
+const IV: [u8; 8] = [2; 8];
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Gives `owner` `s` other patterns, so that lookups in their `PatternityOwned` are as long as
+/// they get.
+fn fill_owned<T: Config>(owner: &T::AccountId, s: u32) {
+	let owned: Vec<T::Hash> = (0..s).map(|i| T::Hashing::hash_of(&i)).collect();
+	let owned: BoundedVec<_, T::MaxPatternityOwned> =
+		owned.try_into().expect("s is below MaxPatternityOwned");
+	<PatternityOwned<T>>::insert(owner, owned);
+}
+
+/// Mints a pattern for `owner`, last in their `PatternityOwned`.
+fn mint<T: Config>(owner: &T::AccountId) -> T::Hash {
+	ThePatternities::<T>::create_pattern(
+		RawOrigin::Signed(owner.clone()).into(),
+		None,
+		ThePatternities::<T>::key_hash(&KEY, &IV),
+		[0; SEALED_KEY_LEN],
+		Permill::zero(),
+		None,
+	)
+	.expect("pattern can be minted");
+
+	*ThePatternities::<T>::patternity_owned(owner)
+		.last()
+		.expect("pattern was just minted")
+}
+
+/// An amount well above the existential deposit.
+fn amount<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(1000u32.into())
+}
+
+/// Gives `who` enough to pay or reserve [`amount`] several times over.
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, amount::<T>().saturating_mul(10u32.into()));
+}
+
+/// A string as long as metadata strings get.
+fn max_string<T: Config>() -> Vec<u8> {
+	vec![b'x'; T::StringLimit::get() as usize]
+}
+
+/// A block a few blocks ahead, for offers and auctions.
+fn later<T: Config>() -> T::BlockNumber {
+	<frame_system::Pallet<T>>::block_number().saturating_add(10u32.into())
+}
+
+benchmarks! {
+	create_pattern {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&caller, s);
+		let key_hash = ThePatternities::<T>::key_hash(&KEY, &IV);
+		let sealed_key = [0; SEALED_KEY_LEN];
+		let price = Some(amount::<T>());
+	}: _(RawOrigin::Signed(caller.clone()), price, key_hash, sealed_key, Permill::zero(), None)
+	verify {
+		let pattern_id = *ThePatternities::<T>::patternity_owned(&caller).last().unwrap();
+		assert_eq!(ThePatternities::<T>::patternity_owned(&caller).len() as u32, s + 1);
+		assert_eq!(ThePatternities::<T>::for_sale(pattern_id), price);
+	}
+
+	set_price {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&caller, s);
+		let pattern_id = mint::<T>(&caller);
+		let price = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller), pattern_id, Some(price))
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().price, Some(price));
+	}
+
+	transfer {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fill_owned::<T>(&caller, s);
+		let pattern_id = mint::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), recipient.clone(), pattern_id, [0; SEALED_KEY_LEN])
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().owner, recipient);
+	}
+
+	buy_pattern {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let seller: T::AccountId = account("seller", 0, 0);
+		let buyer: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&seller, s);
+		let pattern_id = mint::<T>(&seller);
+
+		let price = amount::<T>();
+		T::Currency::make_free_balance_be(&seller, T::Currency::minimum_balance());
+		let fee_destination = T::FeeDestination::get();
+		T::Currency::make_free_balance_be(&fee_destination, T::Currency::minimum_balance());
+		fund::<T>(&buyer);
+		ThePatternities::<T>::set_price(RawOrigin::Signed(seller).into(), pattern_id, Some(price))?;
+	}: _(RawOrigin::Signed(buyer.clone()), pattern_id, price)
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().owner, buyer);
+	}
+
+	create_collection {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = vec![b'x'; T::MaxCollectionNameLen::get() as usize];
+		let collection_id = ThePatternities::<T>::next_collection_id();
+	}: _(RawOrigin::Signed(caller.clone()), name, Some(10), amount::<T>())
+	verify {
+		assert_eq!(ThePatternities::<T>::collection(collection_id).unwrap().owner, caller);
+	}
+
+	create_pattern_in_collection {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&caller, s);
+		fund::<T>(&caller);
+		T::Currency::make_free_balance_be(&owner, T::Currency::minimum_balance());
+
+		let collection_id = ThePatternities::<T>::next_collection_id();
+		ThePatternities::<T>::create_collection(
+			RawOrigin::Signed(owner).into(),
+			b"collection".to_vec(),
+			None,
+			amount::<T>(),
+		)?;
+		let key_hash = ThePatternities::<T>::key_hash(&KEY, &IV);
+		let sealed_key = [0; SEALED_KEY_LEN];
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		collection_id,
+		Some(amount::<T>()),
+		key_hash,
+		sealed_key,
+		Permill::zero(),
+		None
+	)
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity_owned(&caller).len() as u32, s + 1);
+		assert_eq!(ThePatternities::<T>::collection(collection_id).unwrap().minted, 1);
+	}
+
+	set_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let pattern_id = mint::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), pattern_id, max_string::<T>(), max_string::<T>())
+	verify {
+		assert!(ThePatternities::<T>::metadata(&pattern_id).is_some());
+	}
+
+	set_attribute {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let pattern_id = mint::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), pattern_id, max_string::<T>(), Some(max_string::<T>()))
+	verify {
+		assert_eq!(ThePatternities::<T>::metadata(&pattern_id).unwrap().attributes.len(), 1);
+	}
+
+	freeze_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let pattern_id = mint::<T>(&caller);
+		ThePatternities::<T>::set_metadata(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			max_string::<T>(),
+			max_string::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), pattern_id)
+	verify {
+		assert!(ThePatternities::<T>::metadata(&pattern_id).unwrap().frozen);
+	}
+
+	disclose_traits {
+		let caller: T::AccountId = whitelisted_caller();
+		let pattern_id = mint::<T>(&caller);
+		// The seed salt is drawn at the start of the next block.
+		let next = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+		<frame_system::Pallet<T>>::set_block_number(next);
+		ThePatternities::<T>::on_initialize(next);
+	}: _(RawOrigin::Signed(caller), pattern_id, KEY, IV)
+	verify {
+		assert!(ThePatternities::<T>::pattern_traits(&pattern_id).is_some());
+	}
+
+	register_generator {
+		let p in 1 .. T::MaxProgramLen::get();
+		let code = vec![1u8; p as usize];
+	}: _(RawOrigin::Root, 1, ProgramKind::Wasm, code)
+	verify {
+		assert!(ThePatternities::<T>::generator(1).is_some());
+	}
+
+	deprecate_generator {
+		ThePatternities::<T>::register_generator(
+			RawOrigin::Root.into(),
+			1,
+			ProgramKind::Wasm,
+			vec![1],
+		)?;
+	}: _(RawOrigin::Root, 1)
+	verify {
+		assert!(ThePatternities::<T>::generator(1).unwrap().deprecated);
+	}
+
+	set_default_generator {
+		ThePatternities::<T>::register_generator(
+			RawOrigin::Root.into(),
+			1,
+			ProgramKind::Wasm,
+			vec![1],
+		)?;
+	}: _(RawOrigin::Root, 1)
+	verify {
+		assert_eq!(ThePatternities::<T>::default_generator(), 1);
+	}
+
+	reseal {
+		let caller: T::AccountId = whitelisted_caller();
+		let buyer: T::AccountId = account("buyer", 0, 0);
+		let pattern_id = mint::<T>(&caller);
+
+		let price = amount::<T>();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		let fee_destination = T::FeeDestination::get();
+		T::Currency::make_free_balance_be(&fee_destination, T::Currency::minimum_balance());
+		fund::<T>(&buyer);
+		ThePatternities::<T>::set_price(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			Some(price),
+		)?;
+		ThePatternities::<T>::buy_pattern(RawOrigin::Signed(buyer).into(), pattern_id, price)?;
+	}: _(RawOrigin::Signed(caller), pattern_id, [0; SEALED_KEY_LEN])
+	verify {
+		assert_eq!(ThePatternities::<T>::pending_reseal(&pattern_id), None);
+		assert!(ThePatternities::<T>::escrow(&pattern_id).is_none());
+	}
+
+	cancel_sale {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let seller: T::AccountId = account("seller", 0, 0);
+		let buyer: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&seller, s);
+		let pattern_id = mint::<T>(&seller);
+
+		let price = amount::<T>();
+		fund::<T>(&buyer);
+		ThePatternities::<T>::set_price(
+			RawOrigin::Signed(seller.clone()).into(),
+			pattern_id,
+			Some(price),
+		)?;
+		ThePatternities::<T>::buy_pattern(
+			RawOrigin::Signed(buyer.clone()).into(),
+			pattern_id,
+			price,
+		)?;
+		let deadline = ThePatternities::<T>::escrow(&pattern_id).unwrap().deadline;
+		<frame_system::Pallet<T>>::set_block_number(deadline);
+	}: _(RawOrigin::Signed(buyer), pattern_id)
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().owner, seller);
+	}
+
+	burn {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let o in 0 .. T::MaxOffers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&caller, s);
+		fund::<T>(&caller);
+
+		let collection_id = ThePatternities::<T>::next_collection_id();
+		ThePatternities::<T>::create_collection(
+			RawOrigin::Signed(caller.clone()).into(),
+			b"collection".to_vec(),
+			None,
+			amount::<T>(),
+		)?;
+		ThePatternities::<T>::create_pattern_in_collection(
+			RawOrigin::Signed(caller.clone()).into(),
+			collection_id,
+			None,
+			ThePatternities::<T>::key_hash(&KEY, &IV),
+			[0; SEALED_KEY_LEN],
+			Permill::zero(),
+			None,
+		)?;
+		let pattern_id = *ThePatternities::<T>::patternity_owned(&caller).last().unwrap();
+
+		let next = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+		<frame_system::Pallet<T>>::set_block_number(next);
+		ThePatternities::<T>::on_initialize(next);
+		ThePatternities::<T>::disclose_traits(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			KEY,
+			IV,
+		)?;
+		ThePatternities::<T>::set_metadata(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			max_string::<T>(),
+			max_string::<T>(),
+		)?;
+		for i in 0 .. o {
+			let bidder: T::AccountId = account("bidder", i, 0);
+			fund::<T>(&bidder);
+			ThePatternities::<T>::make_offer(
+				RawOrigin::Signed(bidder).into(),
+				pattern_id,
+				amount::<T>(),
+				later::<T>(),
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), pattern_id)
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id), None);
+		assert_eq!(ThePatternities::<T>::patternity_owned(&caller).len() as u32, s);
+		assert_eq!(ThePatternities::<T>::offer_cnt(&pattern_id), 0);
+	}
+
+	make_offer {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let pattern_id = mint::<T>(&owner);
+		// Replacing an earlier offer releases it first.
+		ThePatternities::<T>::make_offer(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			T::Currency::minimum_balance(),
+			later::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), pattern_id, amount::<T>(), later::<T>())
+	verify {
+		let offer = ThePatternities::<T>::offer(&pattern_id, &caller).unwrap();
+		assert_eq!(offer.amount, amount::<T>());
+	}
+
+	withdraw_offer {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let pattern_id = mint::<T>(&owner);
+		ThePatternities::<T>::make_offer(
+			RawOrigin::Signed(caller.clone()).into(),
+			pattern_id,
+			amount::<T>(),
+			later::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), pattern_id)
+	verify {
+		assert_eq!(ThePatternities::<T>::offer(&pattern_id, &caller), None);
+	}
+
+	prune_offer {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let bidder: T::AccountId = account("bidder", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&bidder);
+		let pattern_id = mint::<T>(&owner);
+		let expires_at = later::<T>();
+		ThePatternities::<T>::make_offer(
+			RawOrigin::Signed(bidder.clone()).into(),
+			pattern_id,
+			amount::<T>(),
+			expires_at,
+		)?;
+		<frame_system::Pallet<T>>::set_block_number(expires_at);
+	}: _(RawOrigin::Signed(caller), pattern_id, bidder.clone())
+	verify {
+		assert_eq!(ThePatternities::<T>::offer(&pattern_id, &bidder), None);
+	}
+
+	accept_offer {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let bidder: T::AccountId = account("bidder", 0, 0);
+		fill_owned::<T>(&caller, s);
+		let pattern_id = mint::<T>(&caller);
+
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		let fee_destination = T::FeeDestination::get();
+		T::Currency::make_free_balance_be(&fee_destination, T::Currency::minimum_balance());
+		fund::<T>(&bidder);
+		ThePatternities::<T>::make_offer(
+			RawOrigin::Signed(bidder.clone()).into(),
+			pattern_id,
+			amount::<T>(),
+			later::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), pattern_id, bidder.clone(), [0; SEALED_KEY_LEN])
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().owner, bidder);
+	}
+
+	start_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let pattern_id = mint::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), pattern_id, amount::<T>(), later::<T>())
+	verify {
+		assert!(ThePatternities::<T>::auction(&pattern_id).is_some());
+	}
+
+	bid {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let outbid: T::AccountId = account("bidder", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let pattern_id = mint::<T>(&owner);
+		ThePatternities::<T>::start_auction(
+			RawOrigin::Signed(owner).into(),
+			pattern_id,
+			T::Currency::minimum_balance(),
+			later::<T>(),
+		)?;
+
+		// Outbidding releases the previous highest bid.
+		fund::<T>(&outbid);
+		fund::<T>(&caller);
+		ThePatternities::<T>::bid(
+			RawOrigin::Signed(outbid).into(),
+			pattern_id,
+			T::Currency::minimum_balance(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), pattern_id, amount::<T>())
+	verify {
+		let auction = ThePatternities::<T>::auction(&pattern_id).unwrap();
+		assert_eq!(auction.highest_bid, Some((caller, amount::<T>())));
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let pattern_id = mint::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), pattern_id, Some(delegate.clone()))
+	verify {
+		assert_eq!(ThePatternities::<T>::approved(&pattern_id), Some(delegate));
+	}
+
+	set_approval_for_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(ThePatternities::<T>::is_operator(&caller, &operator));
+	}
+
+	transfer_from {
+		let s in 0 .. T::MaxPatternityOwned::get() - 1;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fill_owned::<T>(&owner, s);
+		let pattern_id = mint::<T>(&owner);
+		// An operator is only checked after the owner and the approved delegate.
+		ThePatternities::<T>::set_approval_for_all(
+			RawOrigin::Signed(owner.clone()).into(),
+			caller.clone(),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller), owner, recipient.clone(), pattern_id, [0; SEALED_KEY_LEN])
+	verify {
+		assert_eq!(ThePatternities::<T>::patternity(&pattern_id).unwrap().owner, recipient);
+	}
+
+	end_auctions {
+		let a in 0 .. T::MaxAuctionsEnding::get();
+		let end = later::<T>();
+		let fee_destination = T::FeeDestination::get();
+		T::Currency::make_free_balance_be(&fee_destination, T::Currency::minimum_balance());
+
+		let mut patterns = Vec::new();
+		for i in 0 .. a {
+			let owner: T::AccountId = account("owner", i, 0);
+			let bidder: T::AccountId = account("bidder", i, 0);
+			let pattern_id = mint::<T>(&owner);
+			T::Currency::make_free_balance_be(&owner, T::Currency::minimum_balance());
+			ThePatternities::<T>::start_auction(
+				RawOrigin::Signed(owner).into(),
+				pattern_id,
+				T::Currency::minimum_balance(),
+				end,
+			)?;
+			fund::<T>(&bidder);
+			ThePatternities::<T>::bid(RawOrigin::Signed(bidder).into(), pattern_id, amount::<T>())?;
+			patterns.push(pattern_id);
+		}
+	}: {
+		ThePatternities::<T>::end_auctions(end);
+	}
+	verify {
+		for (i, pattern_id) in patterns.iter().enumerate() {
+			let bidder: T::AccountId = account("bidder", i as u32, 0);
+			assert_eq!(ThePatternities::<T>::patternity(pattern_id).unwrap().owner, bidder);
+		}
+	}
+
+	draw_seed_salts {
+		let s in 0 .. 1_000;
+		for i in 0 .. s {
+			<Unsalted<T>>::insert(T::Hashing::hash_of(&i), ());
+		}
+	}: {
+		ThePatternities::<T>::draw_seed_salts();
+	}
+	verify {
+		assert_eq!(<Unsalted<T>>::iter().count(), 0);
+		assert_eq!(<SeedSalts<T>>::iter().count() as u32, s);
+	}
+
+	impl_benchmark_test_suite!(ThePatternities, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -5,52 +5,391 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+pub mod migrations;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use enocoro128v2::Enocoro128;
+	use crate::WeightInfo;
 	use frame_support::pallet_prelude::*;
 	use frame_support::{
-		sp_runtime::traits::Hash,
-		traits::{tokens::ExistenceRequirement, Currency},
-		transactional,
+		sp_runtime::{
+			traits::{Hash, Saturating, Zero},
+			PerThing, Permill,
+		},
+		storage::bounded_btree_map::BoundedBTreeMap,
+		traits::{
+			tokens::{BalanceStatus, ExistenceRequirement},
+			Currency, Randomness, ReservableCurrency, StorageVersion,
+		},
+		transactional, Hashable,
 	};
 	use frame_system::pallet_prelude::*;
-	use nanorand::{Rng, WyRand};
+	use patternities_primitives::seed_from_key;
+	use rand::{Rng, SeedableRng};
+	use rand_chacha::ChaCha20Rng;
 	use sp_std::vec::Vec;
 
+	/// The current storage version, see [`crate::migrations`].
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
 	type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// Length of a sealed key: the ephemeral public key, the encrypted key and iv, and the
+	/// authentication tag.
+	pub const SEALED_KEY_LEN: usize = 32 + 16 + 8 + 16;
+
+	/// A pattern's key and iv, sealed to the public key of its owner. The runtime never sees the
+	/// plaintext; sealing and unsealing happen off-chain.
+	pub type SealedKey = [u8; SEALED_KEY_LEN];
+
+	/// A pattern's key and iv in plaintext.
+	pub type KeyMaterial = ([u8; 16], [u8; 8]);
+
+	/// The generator program every pattern was drawn with before programs were registered on
+	/// chain. It takes the seed of a pattern as its only argument, see [`Pallet::seed_of`].
+	pub const BUILTIN_PROGRAM: &str = "
+		use tiny_skia::*;
+		use rand::prelude::*;
+		use rand_chacha::ChaCha20Rng;
+		use std::env;
+
+		fn main() {
+			let args: Vec<String> = env::args().collect();
+
+			let seed_arg = &args[1];
+
+			let seed = seed_arg.parse::<u64>().expect(\"Error parsing seed\");
+
+			let triangle = crate_triangle(seed);
+
+			let mut paint = Paint::default();
+			paint.anti_alias = true;
+			paint.shader = Pattern::new(
+				triangle.as_ref(),
+				SpreadMode::Repeat,
+				FilterQuality::Bicubic,
+				1.0,
+				Transform::from_row(1.5, -0.4, 0.0, -0.8, 5.0, 1.0),
+			);
+
+			let path = PathBuilder::from_circle(200.0, 200.0, 180.0).unwrap();
+
+			let mut pixmap = Pixmap::new(400, 400).unwrap();
+			pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+			pixmap.save_png(format!(\"{}-pattern.png\", seed)).unwrap();
+		}
+
+		fn crate_triangle(hash: u64) -> Pixmap {
+			let mut rng = ChaCha20Rng::seed_from_u64(hash);
+
+			let r: u8 = rng.gen_range(0..255);
+			let g: u8 = rng.gen_range(0..255);
+			let b: u8 = rng.gen_range(0..255);
+
+
+			let mut paint = Paint::default();
+			paint.set_color_rgba8(r, g, b, 255);
+			paint.anti_alias = true;
+
+			let start: u8 = rng.gen_range(0..10);
+			let end: u8 = rng.gen_range(10..25);
+
+			let start_second: u8 = rng.gen_range(25..50);
+			let end_second: u8 = rng.gen_range(50..75);
+
+			let start_third: u8 = rng.gen_range(10..20);
+			let end_third: u8 = rng.gen_range(0..20);
+
+			let mut pb = PathBuilder::new();
+			pb.move_to(start as f32, end as f32);
+			pb.line_to(start_second as f32, end_second as f32);
+			pb.line_to(start_third as f32, end_third as f32);
+
+			let path = pb.finish().unwrap();
+
+			let mut pixmap = Pixmap::new(20, 20).unwrap();
+			pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+			pixmap
+		}";
+
+	/// Version tag of a generator, see [`Generators`].
+	pub type GeneratorVersion = u32;
+
+	/// The version of [`BUILTIN_PROGRAM`], the default generator until root sets another one.
+	pub const BUILTIN_GENERATOR: GeneratorVersion = 0;
+
+	/// Identifier of a collection.
+	pub type CollectionId = u32;
+
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	#[codec(mel_bound())]
 	pub struct PatternSeed<T: Config> {
-		pub key: [u8; 16],
-		pub iv: [u8; 8],
-		pub cipher: BoundedVec<u8, T::MaxByteCipher>,
+		/// Commitment to the key and iv, see [`Pallet::key_hash`].
+		pub key_hash: [u8; 32],
+		/// The generator that draws the pattern from the seed in its key. Generators are never
+		/// removed, so the pattern stays renderable.
+		pub generator: GeneratorVersion,
 		pub price: Option<BalanceOf<T>>,
 		pub owner: AccountOf<T>,
+		/// The account that minted the pattern, paid `royalty` of every later sale.
+		pub creator: AccountOf<T>,
+		pub royalty: Permill,
+		/// The collection the pattern was minted in, if any.
+		pub collection: Option<CollectionId>,
+	}
+
+	/// A standing offer on a pattern. The amount is reserved from the bidder until the offer is
+	/// accepted or withdrawn, or pruned once it expires.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Offer<T: Config> {
+		pub amount: BalanceOf<T>,
+		/// The offer can no longer be accepted from this block on.
+		pub expires_at: T::BlockNumber,
+	}
+
+	/// The payment for a pattern sold without its key, reserved from the buyer until the seller
+	/// re-seals the key to them.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Escrow<T: Config> {
+		pub seller: AccountOf<T>,
+		pub amount: BalanceOf<T>,
+		/// From this block on, the buyer can cancel the sale if the key was not re-sealed.
+		pub deadline: T::BlockNumber,
+	}
+
+	/// A named series of patterns. Anyone can mint into a collection by paying its mint price to
+	/// the collection owner, until `max_supply` patterns have been minted.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Collection<T: Config> {
+		pub owner: AccountOf<T>,
+		pub name: BoundedVec<u8, T::MaxCollectionNameLen>,
+		pub max_supply: Option<u32>,
+		pub mint_price: BalanceOf<T>,
+		/// The number of patterns minted in the collection, including burned ones.
+		pub minted: u32,
+	}
+
+	pub type MetadataString<T> = BoundedVec<u8, <T as Config>::StringLimit>;
+
+	/// Owner-settable metadata of a pattern. A deposit proportional to its encoded size is
+	/// reserved from the account that last changed it, until the pattern is burned.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct PatternMetadata<T: Config> {
+		pub name: MetadataString<T>,
+		pub description: MetadataString<T>,
+		pub attributes: BoundedBTreeMap<MetadataString<T>, MetadataString<T>, T::MaxAttributes>,
+		/// Once frozen, the metadata can no longer be changed, not even by later owners.
+		pub frozen: bool,
+		/// The account `deposit` is reserved from. The deposit is returned to it when the pattern
+		/// changes hands, and the next update is charged to the new owner.
+		pub depositor: AccountOf<T>,
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// An English auction on a pattern. The highest bid is reserved from its bidder until the
+	/// bidder is outbid or the auction is settled.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Auction<T: Config> {
+		pub reserve_price: BalanceOf<T>,
+		/// Bids are accepted until this block, at the start of which the auction is settled.
+		pub end: T::BlockNumber,
+		pub highest_bid: Option<(AccountOf<T>, BalanceOf<T>)>,
+	}
+
+	/// The visual traits of a pattern, fully determined by its generator seed.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub struct PatternTraits {
+		/// Colour of the triangle, as r, g and b.
+		pub color: [u8; 3],
+		/// Corners of the triangle within its 20x20 tile, as x and y.
+		pub vertices: [[u8; 2]; 3],
+	}
+
+	impl PatternTraits {
+		/// The colour band of the triangle: each channel quantized to four levels.
+		pub fn color_band(&self) -> u8 {
+			let [r, g, b] = self.color;
+			(r / 64) << 4 | (g / 64) << 2 | (b / 64)
+		}
+
+		/// The size class of the triangle, from 0 for the smallest to 3 for the largest.
+		pub fn area_class(&self) -> u8 {
+			let [[x1, y1], [x2, y2], [x3, y3]] = self.vertices.map(|v| v.map(i32::from));
+			let double_area = ((x2 - x1) * (y3 - y1) - (x3 - x1) * (y2 - y1)).abs();
+
+			match double_area {
+				0..=199 => 0,
+				200..=499 => 1,
+				500..=999 => 2,
+				_ => 3,
+			}
+		}
+
+		/// The buckets counted in the trait distribution.
+		pub fn buckets(&self) -> [TraitBucket; 2] {
+			[TraitBucket::ColorBand(self.color_band()), TraitBucket::AreaClass(self.area_class())]
+		}
+	}
+
+	/// A class of patterns sharing a visual trait, counted to rank their rarity.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TraitBucket {
+		ColorBand(u8),
+		AreaClass(u8),
+	}
+
+	/// How the code of a generator is run.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum ProgramKind {
+		/// Rust source taking the seed as its only argument, like [`BUILTIN_PROGRAM`].
+		Source,
+		/// A deterministic WASM module without imports. It exports its `memory` and
+		/// `render(seed: i64, width: i32, height: i32) -> i32`, which returns the offset of the
+		/// `width * height * 4` bytes of RGBA it rendered, or 0 if it cannot render that size.
+		Wasm,
+	}
+
+	/// A version of the generator registered by root.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Generator<T: Config> {
+		/// The code of the generator in [`GeneratorPrograms`].
+		pub program: T::Hash,
+		pub kind: ProgramKind,
+		/// Deprecated generators can no longer be minted with, but still render their patterns.
+		pub deprecated: bool,
+	}
+
+	/// Whether the owner of a pattern can unseal its key.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum KeyStatus<AccountId> {
+		/// The key is sealed to the current owner.
+		Sealed,
+		/// The key still has to be re-sealed by the given previous owner.
+		PendingReseal(AccountId),
+	}
+
+	/// A pattern as served to clients by the runtime API. This is decoupled from
+	/// [`PatternSeed`], so its encoding stays the same when the storage layout is migrated.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub struct PatternInfo<AccountId, Balance, Hash> {
+		pub owner: AccountId,
+		pub price: Option<Balance>,
+		pub key_hash: [u8; 32],
+		pub generator: GeneratorVersion,
+		/// The code of the generator in [`GeneratorPrograms`].
+		pub program: Hash,
+		pub key_status: KeyStatus<AccountId>,
+		pub creator: AccountId,
+		pub royalty: Permill,
+		pub collection: Option<CollectionId>,
+		/// Mixed into the seed of the pattern, see [`Pallet::seed_of`].
+		pub seed_salt: Option<[u8; 32]>,
 	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Source of consensus-safe randomness used to seed newly minted patterns.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
 		#[pallet::constant]
 		type MaxPatternityOwned: Get<u32>;
+		/// The maximum length of a generator program.
+		#[pallet::constant]
+		type MaxProgramLen: Get<u32>;
+		/// The maximum number of standing offers on a pattern.
+		#[pallet::constant]
+		type MaxOffers: Get<u32>;
+		/// The maximum number of auctions that can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsEnding: Get<u32>;
+		/// How long the seller of a pattern has to re-seal its key to the buyer before the buyer
+		/// can cancel the sale.
+		#[pallet::constant]
+		type ResealPeriod: Get<Self::BlockNumber>;
+		/// The highest royalty a creator can set on their patterns.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
+		/// The share of every sale taken by the marketplace.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Permill>;
+		/// The account marketplace fees are paid to.
+		type FeeDestination: Get<Self::AccountId>;
 		#[pallet::constant]
-		type MaxByteCipher: Get<u32>;
+		type MaxCollectionNameLen: Get<u32>;
+		/// The maximum length of a metadata name, description, attribute key or value.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+		/// The maximum number of attributes of a pattern.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+		/// The base deposit reserved for storing metadata of a pattern.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+		/// The additional deposit reserved per byte of metadata.
+		#[pallet::constant]
+		type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
 	#[pallet::getter(fn patternity)]
 	pub(super) type Patternity<T: Config> = StorageMap<_, Twox64Concat, T::Hash, PatternSeed<T>>;
 
+	/// Generator programs by the hash of their code. Each program is stored once and shared by
+	/// all patterns drawn with it.
+	#[pallet::storage]
+	#[pallet::getter(fn generator_program)]
+	pub(super) type GeneratorPrograms<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, T::MaxProgramLen>>;
+
+	/// Registered versions of the generator.
+	#[pallet::storage]
+	#[pallet::getter(fn generator)]
+	pub(super) type Generators<T: Config> =
+		StorageMap<_, Twox64Concat, GeneratorVersion, Generator<T>>;
+
+	/// The generator patterns are minted with unless they pick another one.
+	#[pallet::storage]
+	#[pallet::getter(fn default_generator)]
+	pub(super) type DefaultGenerator<T: Config> = StorageValue<_, GeneratorVersion, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn patternity_owned)]
 	pub(super) type PatternityOwned<T: Config> = StorageMap<
@@ -65,9 +404,123 @@ pub mod pallet {
 	#[pallet::getter(fn patternity_cnt)]
 	pub(super) type PatternityCnt<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// The key material of each pattern, sealed to its current owner, or to the previous owner
+	/// while the pattern is pending a reseal.
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_key)]
+	pub(super) type SealedKeys<T: Config> = StorageMap<_, Twox64Concat, T::Hash, SealedKey>;
+
+	/// Patterns that changed hands without a sealed key for the new owner, mapped to the previous
+	/// owner who still has to re-seal it.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_reseal)]
+	pub(super) type PendingReseal<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::AccountId>;
+
+	/// Payments held for patterns sold without their key, keyed by pattern. The seller is paid
+	/// when they re-seal the key through `reseal`.
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub(super) type Escrows<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Escrow<T>>;
+
+	/// The salt mixed into the seed of each pattern, see [`Pallet::seed_of`]. It is drawn from
+	/// on-chain randomness in the block after the pattern is minted, so the minter cannot know it
+	/// when choosing the key. Genesis patterns and patterns minted before salts have none.
+	#[pallet::storage]
+	#[pallet::getter(fn seed_salt)]
+	pub(super) type SeedSalts<T: Config> = StorageMap<_, Twox64Concat, T::Hash, [u8; 32]>;
+
+	/// Patterns minted in the current block, whose salt is drawn at the start of the next one.
+	#[pallet::storage]
+	pub(super) type Unsalted<T: Config> = StorageMap<_, Twox64Concat, T::Hash, ()>;
+
+	/// The price of every pattern listed for sale, kept alongside [`PatternSeed::price`] so
+	/// listings can be paged without reading every pattern.
+	#[pallet::storage]
+	#[pallet::getter(fn for_sale)]
+	pub(super) type ForSale<T: Config> = StorageMap<_, Twox64Concat, T::Hash, BalanceOf<T>>;
+
+	/// Standing offers on patterns, keyed by pattern and bidder.
+	#[pallet::storage]
+	#[pallet::getter(fn offer)]
+	pub(super) type Offers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, Offer<T>>;
+
+	/// The number of standing offers on each pattern.
+	#[pallet::storage]
+	#[pallet::getter(fn offer_cnt)]
+	pub(super) type OfferCnt<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	/// Live auctions, keyed by pattern.
+	#[pallet::storage]
+	#[pallet::getter(fn auction)]
+	pub(super) type Auctions<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Auction<T>>;
+
+	/// Patterns whose auction ends at a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending)]
+	pub(super) type AuctionsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<T::Hash, T::MaxAuctionsEnding>,
+		ValueQuery,
+	>;
+
+	/// The account approved to transfer a pattern on behalf of its owner. Cleared whenever the
+	/// pattern changes hands.
+	#[pallet::storage]
+	#[pallet::getter(fn approved)]
+	pub(super) type Approvals<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::AccountId>;
+
+	/// Operators allowed to transfer every pattern of an owner, keyed by owner and operator.
+	#[pallet::storage]
+	pub(super) type Operators<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection)]
+	pub(super) type Collections<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, Collection<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_collection_id)]
+	pub(super) type NextCollectionId<T: Config> = StorageValue<_, CollectionId, ValueQuery>;
+
+	/// The patterns of each collection, keyed by collection and pattern.
+	#[pallet::storage]
+	pub(super) type CollectionPatterns<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, T::Hash, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn metadata)]
+	pub(super) type Metadata<T: Config> = StorageMap<_, Twox64Concat, T::Hash, PatternMetadata<T>>;
+
+	/// Visual traits of the live patterns whose owners disclosed them, uncounted on burn. Traits
+	/// are counted on disclosure rather than on mint: the runtime never sees a sealed key, so it
+	/// cannot derive the traits of a pattern until its owner publishes the key through
+	/// `disclose_traits`.
+	#[pallet::storage]
+	#[pallet::getter(fn pattern_traits)]
+	pub(super) type Traits<T: Config> = StorageMap<_, Twox64Concat, T::Hash, PatternTraits>;
+
+	/// The number of live patterns with disclosed traits.
+	#[pallet::storage]
+	#[pallet::getter(fn traits_cnt)]
+	pub(super) type TraitsCnt<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The number of live patterns with disclosed traits in each trait bucket.
+	#[pallet::storage]
+	#[pallet::getter(fn trait_bucket_cnt)]
+	pub(super) type TraitBucketCnt<T: Config> =
+		StorageMap<_, Twox64Concat, TraitBucket, u32, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
-		pub the_patternities: Vec<(T::AccountId, Option<BalanceOf<T>>)>,
+		/// Patterns minted at genesis: their owner, price and key and iv. The key is fixed for
+		/// reproducible showcases, see `patternities_primitives::showcase_key_material`, and is
+		/// public in the chain spec, so the pattern is seeded by the key unsalted. Owners seal
+		/// it to themselves with `seal --key --iv` and `reseal`.
+		pub the_patternities: Vec<(T::AccountId, Option<BalanceOf<T>>, KeyMaterial)>,
 	}
 
 	#[cfg(feature = "std")]
@@ -79,9 +532,30 @@ pub mod pallet {
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		/// Genesis cannot seal keys, so every owner is left to seal theirs through `reseal`, and
+		/// priced patterns are only listed for sale from then on. The keys are public in the
+		/// chain spec, so the traits of every pattern are disclosed.
 		fn build(&self) {
-			for (owner, price) in &self.the_patternities {
-				let _ = <Pallet<T>>::mint(owner, price.clone());
+			<Pallet<T>>::register_builtin_generator()
+				.expect("MaxProgramLen fits the builtin program");
+
+			for (owner, price, (key, iv)) in &self.the_patternities {
+				let pattern = <Pallet<T>>::mint(
+					owner,
+					price.clone(),
+					<Pallet<T>>::key_hash(key, iv),
+					Permill::zero(),
+					None,
+					None,
+				)
+				.expect("genesis patterns are valid");
+
+				let pattern_id = <Pallet<T>>::insert_pattern(owner, pattern, None)
+					.expect("genesis patterns fit their owner");
+
+				let traits = <Pallet<T>>::traits_of_seed(seed_from_key(key));
+				<Traits<T>>::insert(pattern_id, traits);
+				<Pallet<T>>::count_traits(&traits);
 			}
 		}
 	}
@@ -92,7 +566,40 @@ pub mod pallet {
 		Created(T::AccountId, T::Hash),
 		PriceSet(T::AccountId, T::Hash, Option<BalanceOf<T>>),
 		Transferred(T::AccountId, T::AccountId, T::Hash),
-		Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		/// A pattern was bought: buyer, seller, pattern, price, and the royalty paid to its
+		/// creator and the marketplace fee taken out of the price.
+		Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		Resealed(T::AccountId, T::Hash),
+		/// A sale whose key was not re-sealed in time was cancelled: buyer, seller and pattern.
+		SaleCancelled(T::AccountId, T::AccountId, T::Hash),
+		Burned(T::AccountId, T::Hash),
+		OfferMade(T::AccountId, T::Hash, BalanceOf<T>, T::BlockNumber),
+		OfferWithdrawn(T::AccountId, T::Hash),
+		/// An expired offer was removed and its amount released to the bidder.
+		OfferPruned(T::AccountId, T::Hash),
+		OfferAccepted(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		AuctionStarted(T::AccountId, T::Hash, BalanceOf<T>, T::BlockNumber),
+		AuctionBid(T::AccountId, T::Hash, BalanceOf<T>),
+		/// An auction ended, with its winner and winning bid if it was sold.
+		AuctionEnded(T::Hash, Option<(T::AccountId, BalanceOf<T>)>),
+		/// A marketplace fee was paid to the fee destination on the sale of a pattern.
+		FeeCollected(T::Hash, BalanceOf<T>),
+		/// The owner of a pattern approved a delegate to transfer it, or cleared the approval.
+		Approved(T::AccountId, T::Hash, Option<T::AccountId>),
+		/// An owner allowed or disallowed an operator to transfer all of their patterns.
+		ApprovalForAll(T::AccountId, T::AccountId, bool),
+		CollectionCreated(T::AccountId, CollectionId),
+		/// A pattern was minted in a collection: minter, collection and pattern.
+		CreatedInCollection(T::AccountId, CollectionId, T::Hash),
+		MetadataSet(T::Hash),
+		/// An attribute of a pattern was set, or cleared with `None`.
+		AttributeSet(T::Hash, Vec<u8>, Option<Vec<u8>>),
+		MetadataFrozen(T::Hash),
+		TraitsDisclosed(T::Hash, PatternTraits),
+		/// A generator was registered: its version, the hash of its code and how it is run.
+		GeneratorRegistered(GeneratorVersion, T::Hash, ProgramKind),
+		GeneratorDeprecated(GeneratorVersion),
+		DefaultGeneratorSet(GeneratorVersion),
 	}
 
 	#[pallet::error]
@@ -107,33 +614,347 @@ pub mod pallet {
 		PatternNotForSale,
 		PatternBidPriceTooLow,
 		NotEnoughBalance,
+		PatternExists,
+		ProgramTooLong,
+		NotPendingReseal,
+		OfferNotExist,
+		OfferExpired,
+		OfferNotExpired,
+		OfferOnOwnPattern,
+		TooManyOffers,
+		PatternInAuction,
+		AuctionNotExist,
+		AuctionEnded,
+		InvalidAuctionEnd,
+		TooManyAuctionsEnding,
+		AuctionBidTooLow,
+		RoyaltyTooHigh,
+		NotApproved,
+		CollectionNotExist,
+		CollectionNameTooLong,
+		CollectionCntOverflow,
+		CollectionSoldOut,
+		MetadataIsFrozen,
+		StringTooLong,
+		TooManyAttributes,
+		InvalidKey,
+		TraitsAlreadyDisclosed,
+		GeneratorExists,
+		GeneratorNotExist,
+		GeneratorIsDeprecated,
+		GeneratorIsDefault,
+		/// Traits are only known for patterns drawn by the builtin generator.
+		TraitsUnknown,
+		/// The pattern cannot change hands or be listed until its key is re-sealed to its owner.
+		KeyPendingReseal,
+		/// The payment for the pattern is still held until its key is re-sealed.
+		PatternInEscrow,
+		NotInEscrow,
+		/// The seller still has time to re-seal the key of the pattern.
+		ResealNotOverdue,
+		/// The seed salt of a pattern is only drawn in the block after it is minted.
+		SeedNotDrawn,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ended = Self::end_auctions(now);
+			let salted = Self::draw_seed_salts();
+
+			T::WeightInfo::end_auctions(ended)
+				.saturating_add(T::WeightInfo::draw_seed_salts(salted))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v5::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			crate::migrations::v5::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			crate::migrations::v5::post_migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(100)]
-		pub fn create_pattern(origin: OriginFor<T>, price: Option<BalanceOf<T>>) -> DispatchResult {
+		/// Mint a pattern from key material generated off-chain. `key_hash` commits to the key
+		/// and iv and `sealed_key` holds them sealed to the sender. The sender is paid `royalty`
+		/// of every later sale of the pattern. The pattern is drawn by `generator`, or the
+		/// default generator if none is given.
+		///
+		/// The seed of the pattern mixes the key with a salt drawn from on-chain randomness in
+		/// the next block, so the sender cannot grind keys for rare traits nor mint a disclosed
+		/// key again as the same pattern. Drawing the salt is charged here.
+		#[pallet::weight(
+			T::WeightInfo::create_pattern(T::MaxPatternityOwned::get())
+				.saturating_add(T::WeightInfo::draw_seed_salts(1))
+		)]
+		pub fn create_pattern(
+			origin: OriginFor<T>,
+			price: Option<BalanceOf<T>>,
+			key_hash: [u8; 32],
+			sealed_key: SealedKey,
+			royalty: Permill,
+			generator: Option<GeneratorVersion>,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let pattern_obj = Self::mint(&sender, price);
+			let pattern_obj = Self::mint(&sender, price, key_hash, royalty, None, generator)?;
+			let pattern_id = Self::insert_pattern(&sender, pattern_obj, Some(sealed_key))?;
+			<Unsalted<T>>::insert(pattern_id, ());
 
-			let pattern_id = T::Hashing::hash_of(&pattern_obj);
+			Self::deposit_event(Event::Created(sender, pattern_id));
+			Ok(())
+		}
 
-			<PatternityOwned<T>>::try_mutate(&sender, |patt_vec| patt_vec.try_push(pattern_id))
-				.map_err(|_| <Error<T>>::ExceedMaxPatternityOwned)?;
+		/// Create a collection owned by the sender. `max_supply` caps the number of patterns
+		/// that can ever be minted in it, and minters pay `mint_price` to the sender.
+		#[pallet::weight(T::WeightInfo::create_collection())]
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			max_supply: Option<u32>,
+			mint_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
-			<Patternity<T>>::insert(pattern_id, pattern_obj);
+			let name: BoundedVec<u8, T::MaxCollectionNameLen> =
+				name.try_into().map_err(|_| <Error<T>>::CollectionNameTooLong)?;
 
-			let new_cnt =
-				Self::patternity_cnt().checked_add(1).ok_or(<Error<T>>::PatternCntOverflow)?;
+			let collection_id = Self::next_collection_id();
+			let next_id = collection_id.checked_add(1).ok_or(<Error<T>>::CollectionCntOverflow)?;
 
-			<PatternityCnt<T>>::put(new_cnt);
+			<Collections<T>>::insert(
+				collection_id,
+				Collection { owner: sender.clone(), name, max_supply, mint_price, minted: 0 },
+			);
+			<NextCollectionId<T>>::put(next_id);
+
+			Self::deposit_event(Event::CollectionCreated(sender, collection_id));
+
+			Ok(())
+		}
+
+		/// Mint a pattern like `create_pattern` into a collection, paying its mint price to the
+		/// collection owner.
+		#[transactional]
+		#[pallet::weight(
+			T::WeightInfo::create_pattern_in_collection(T::MaxPatternityOwned::get())
+				.saturating_add(T::WeightInfo::draw_seed_salts(1))
+		)]
+		pub fn create_pattern_in_collection(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			price: Option<BalanceOf<T>>,
+			key_hash: [u8; 32],
+			sealed_key: SealedKey,
+			royalty: Permill,
+			generator: Option<GeneratorVersion>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::collection(collection_id).ok_or(<Error<T>>::CollectionNotExist)?;
+			if let Some(max_supply) = collection.max_supply {
+				ensure!(collection.minted < max_supply, <Error<T>>::CollectionSoldOut);
+			}
+
+			if sender != collection.owner {
+				T::Currency::transfer(
+					&sender,
+					&collection.owner,
+					collection.mint_price,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+
+			let pattern_obj =
+				Self::mint(&sender, price, key_hash, royalty, Some(collection_id), generator)?;
+			let pattern_id = Self::insert_pattern(&sender, pattern_obj, Some(sealed_key))?;
+			<Unsalted<T>>::insert(pattern_id, ());
+
+			collection.minted =
+				collection.minted.checked_add(1).ok_or(<Error<T>>::CollectionSoldOut)?;
+			<Collections<T>>::insert(collection_id, collection);
+			<CollectionPatterns<T>>::insert(collection_id, pattern_id, ());
+
+			Self::deposit_event(Event::CreatedInCollection(sender, collection_id, pattern_id));
+			Ok(())
+		}
+
+		/// Set the name and description of a pattern owned by the sender.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			name: Vec<u8>,
+			description: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let name: MetadataString<T> = name.try_into().map_err(|_| <Error<T>>::StringTooLong)?;
+			let description: MetadataString<T> =
+				description.try_into().map_err(|_| <Error<T>>::StringTooLong)?;
+
+			Self::update_metadata(&pattern_id, &sender, |metadata| {
+				metadata.name = name;
+				metadata.description = description;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MetadataSet(pattern_id));
 
-			Self::deposit_event(Event::Created(sender, pattern_id));
 			Ok(())
 		}
 
-		#[pallet::weight(100)]
+		/// Set an attribute of a pattern owned by the sender, or clear it with `None`.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::set_attribute())]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			key: Vec<u8>,
+			value: Option<Vec<u8>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let bounded_key: MetadataString<T> =
+				key.clone().try_into().map_err(|_| <Error<T>>::StringTooLong)?;
+			let bounded_value: Option<MetadataString<T>> = value
+				.clone()
+				.map(TryInto::try_into)
+				.transpose()
+				.map_err(|_| <Error<T>>::StringTooLong)?;
+
+			Self::update_metadata(&pattern_id, &sender, |metadata| {
+				match bounded_value {
+					Some(value) => {
+						metadata
+							.attributes
+							.try_insert(bounded_key, value)
+							.map_err(|_| <Error<T>>::TooManyAttributes)?;
+					},
+					None => {
+						metadata.attributes.remove(&bounded_key);
+					},
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AttributeSet(pattern_id, key, value));
+
+			Ok(())
+		}
+
+		/// Freeze the metadata of a pattern owned by the sender, so that it can never change
+		/// again.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::freeze_metadata())]
+		pub fn freeze_metadata(origin: OriginFor<T>, pattern_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::update_metadata(&pattern_id, &sender, |metadata| {
+				metadata.frozen = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MetadataFrozen(pattern_id));
+
+			Ok(())
+		}
+
+		/// Publish the key and iv of a pattern owned by the sender, so that its visual traits
+		/// are derived and stored on chain. The traits determine the rendered image, so this
+		/// makes the pattern public.
+		#[pallet::weight(T::WeightInfo::disclose_traits())]
+		pub fn disclose_traits(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			key: [u8; 16],
+			iv: [u8; 8],
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
+			ensure!(pattern.owner == sender, <Error<T>>::NotPatternOwner);
+			ensure!(Self::key_hash(&key, &iv) == pattern.key_hash, <Error<T>>::InvalidKey);
+			ensure!(pattern.generator == BUILTIN_GENERATOR, <Error<T>>::TraitsUnknown);
+			ensure!(!<Traits<T>>::contains_key(&pattern_id), <Error<T>>::TraitsAlreadyDisclosed);
+
+			let traits = Self::traits_of_seed(Self::pattern_seed(&pattern_id, &key)?);
+			<Traits<T>>::insert(&pattern_id, traits);
+			Self::count_traits(&traits);
+
+			Self::deposit_event(Event::TraitsDisclosed(pattern_id, traits));
+
+			Ok(())
+		}
+
+		/// Register `code` as version `version` of the generator.
+		#[pallet::weight(T::WeightInfo::register_generator(code.len() as u32))]
+		pub fn register_generator(
+			origin: OriginFor<T>,
+			version: GeneratorVersion,
+			kind: ProgramKind,
+			code: Vec<u8>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(!<Generators<T>>::contains_key(version), <Error<T>>::GeneratorExists);
+
+			let program = Self::register_program(&code)?;
+			<Generators<T>>::insert(version, Generator { program, kind, deprecated: false });
+
+			Self::deposit_event(Event::GeneratorRegistered(version, program, kind));
+
+			Ok(())
+		}
+
+		/// Stop minting with a generator. Its patterns keep rendering with it.
+		#[pallet::weight(T::WeightInfo::deprecate_generator())]
+		pub fn deprecate_generator(
+			origin: OriginFor<T>,
+			version: GeneratorVersion,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(version != Self::default_generator(), <Error<T>>::GeneratorIsDefault);
+			<Generators<T>>::try_mutate(version, |generator| -> DispatchResult {
+				let generator = generator.as_mut().ok_or(<Error<T>>::GeneratorNotExist)?;
+				generator.deprecated = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::GeneratorDeprecated(version));
+
+			Ok(())
+		}
+
+		/// Mint patterns that pick no generator with `version`.
+		#[pallet::weight(T::WeightInfo::set_default_generator())]
+		pub fn set_default_generator(
+			origin: OriginFor<T>,
+			version: GeneratorVersion,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let generator = Self::generator(version).ok_or(<Error<T>>::GeneratorNotExist)?;
+			ensure!(!generator.deprecated, <Error<T>>::GeneratorIsDeprecated);
+			<DefaultGenerator<T>>::put(version);
+
+			Self::deposit_event(Event::DefaultGeneratorSet(version));
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_price(T::MaxPatternityOwned::get()))]
 		pub fn set_price(
 			origin: OriginFor<T>,
 			pattern_id: T::Hash,
@@ -142,11 +963,17 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 
 			ensure!(Self::is_pattern_owner(&pattern_id, &sender)?, <Error<T>>::NotPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
 
 			let mut pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
 
 			pattern.price = new_price.clone();
 			<Patternity<T>>::insert(&pattern_id, pattern);
+			match new_price {
+				Some(price) => <ForSale<T>>::insert(&pattern_id, price),
+				None => <ForSale<T>>::remove(&pattern_id),
+			}
 
 			// Deposit a "PriceSet" event.
 			Self::deposit_event(Event::PriceSet(sender, pattern_id, new_price));
@@ -154,15 +981,19 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(100)]
+		/// Transfer a pattern, handing over its key re-sealed to the recipient.
+		#[pallet::weight(T::WeightInfo::transfer(T::MaxPatternityOwned::get()))]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: T::AccountId,
 			pattern_id: T::Hash,
+			sealed_key: SealedKey,
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 
 			ensure!(Self::is_pattern_owner(&pattern_id, &from)?, <Error<T>>::NotPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
 
 			ensure!(from != to, <Error<T>>::TransferToSelf);
 
@@ -172,7 +1003,7 @@ pub mod pallet {
 				<Error<T>>::ExceedMaxPatternityOwned
 			);
 
-			Self::transfer_pattern_to(&pattern_id, &to)?;
+			Self::transfer_pattern_to(&pattern_id, &to, Some(sealed_key))?;
 
 			Self::deposit_event(Event::Transferred(from, to, pattern_id));
 
@@ -180,7 +1011,7 @@ pub mod pallet {
 		}
 
 		#[transactional]
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::buy_pattern(T::MaxPatternityOwned::get()))]
 		pub fn buy_pattern(
 			origin: OriginFor<T>,
 			pattern_id: T::Hash,
@@ -190,6 +1021,8 @@ pub mod pallet {
 
 			let pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
 			ensure!(pattern.owner != buyer, <Error<T>>::BuyerIsPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
 
 			if let Some(ask_price) = pattern.price {
 				ensure!(ask_price <= bid_price, <Error<T>>::PatternBidPriceTooLow);
@@ -206,213 +1039,637 @@ pub mod pallet {
 			);
 
 			let seller = pattern.owner.clone();
+			let (royalty, fee) = Self::cut_of_sale(&pattern, &seller, bid_price);
 
-			T::Currency::transfer(&buyer, &seller, bid_price, ExistenceRequirement::KeepAlive)?;
+			T::Currency::reserve(&buyer, bid_price).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			Self::transfer_pattern_to(&pattern_id, &buyer, None)?;
+			Self::hold_in_escrow(&pattern_id, seller.clone(), bid_price);
 
-			Self::transfer_pattern_to(&pattern_id, &buyer)?;
-
-			Self::deposit_event(Event::Bought(buyer, seller, pattern_id, bid_price));
+			Self::deposit_event(Event::Bought(buyer, seller, pattern_id, bid_price, royalty, fee));
 
 			Ok(())
 		}
-	}
-
-	impl<T: Config> Pallet<T> {
-		fn generate_random_key() -> [u8; 16] {
-			let mut rng = WyRand::new();
-
-			let key: [u8; 16] = [
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-			];
-
-			let iv: [u8; 8] = [
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-			];
-
-			let mut e128 = Enocoro128::new(&key, &iv);
-
-			let ret: [u8; 16] = [
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-			];
-
-			ret
-		}
-
-		fn generate_random_iv() -> [u8; 8] {
-			let mut rng = WyRand::new();
-
-			let key: [u8; 16] = [
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-			];
-
-			let iv: [u8; 8] = [
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-				rng.generate::<u8>(),
-			];
-
-			let mut e128 = Enocoro128::new(&key, &iv);
-
-			let ret: [u8; 8] = [
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-				e128.rand_u8(),
-			];
-
-			ret
-		}
-
-		fn cipher_code(key: [u8; 16], iv: [u8; 8]) -> [u8; 2100] {
-			let mut text_base = "
-					use tiny_skia::*;
-					use rand::prelude::*;
-					use rand_chacha::ChaCha20Rng;
-					use std::env;
-
-					fn main() {
-						let args: Vec<String> = env::args().collect();
-
-						let seed_arg = &args[1];
-
-						let seed = seed_arg.parse::<u64>().expect(\"Error parsing seed\");
-
-						let triangle = crate_triangle(seed);
-
-						let mut paint = Paint::default();
-						paint.anti_alias = true;
-						paint.shader = Pattern::new(
-							triangle.as_ref(),
-							SpreadMode::Repeat,
-							FilterQuality::Bicubic,
-							1.0,
-							Transform::from_row(1.5, -0.4, 0.0, -0.8, 5.0, 1.0),
-						);
-
-						let path = PathBuilder::from_circle(200.0, 200.0, 180.0).unwrap();
-
-						let mut pixmap = Pixmap::new(400, 400).unwrap();
-						pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
-						pixmap.save_png(format!(\"{}-pattern.png\", seed)).unwrap();
-					}
-
-					fn crate_triangle(hash: u64) -> Pixmap {
-						let mut rng = ChaCha20Rng::seed_from_u64(hash);
 
-						let r: u8 = rng.gen_range(0..255);
-						let g: u8 = rng.gen_range(0..255);
-						let b: u8 = rng.gen_range(0..255);
+		/// Hand the key of a pattern sold by the sender over to its new owner, which pays the
+		/// sender the price held in escrow, or seal the key of a pattern minted at genesis to its
+		/// owner. A pattern priced before its key was sealed is listed for sale from then on.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::reseal())]
+		pub fn reseal(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			sealed_key: SealedKey,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
+			ensure!(
+				Self::pending_reseal(&pattern_id) == Some(sender.clone()),
+				<Error<T>>::NotPendingReseal
+			);
 
-						let mut paint = Paint::default();
-						paint.set_color_rgba8(r, g, b, 255);
-						paint.anti_alias = true;
+			<SealedKeys<T>>::insert(&pattern_id, sealed_key);
+			<PendingReseal<T>>::remove(&pattern_id);
 
-						let start: u8 = rng.gen_range(0..10);
-						let end: u8 = rng.gen_range(10..25);
+			let pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
+			if let Some(escrow) = <Escrows<T>>::take(&pattern_id) {
+				Self::pay_for_pattern(
+					&pattern_id,
+					&pattern,
+					&escrow.seller,
+					&pattern.owner,
+					escrow.amount,
+				)?;
+			}
+			if let Some(price) = pattern.price {
+				<ForSale<T>>::insert(&pattern_id, price);
+			}
 
-						let start_second: u8 = rng.gen_range(25..50);
-						let end_second: u8 = rng.gen_range(50..75);
+			Self::deposit_event(Event::Resealed(sender, pattern_id));
 
-						let start_third: u8 = rng.gen_range(10..20);
-						let end_third: u8 = rng.gen_range(0..20);
+			Ok(())
+		}
 
-						let mut pb = PathBuilder::new();
-						pb.move_to(start as f32, end as f32);
-						pb.line_to(start_second as f32, end_second as f32);
-						pb.line_to(start_third as f32, end_third as f32);
+		/// Cancel the purchase of a pattern owned by the sender whose key the seller did not
+		/// re-seal within `ResealPeriod`. The payment held in escrow is released to the sender
+		/// and the pattern returns to the seller, whose sealed key is still in place.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::cancel_sale(T::MaxPatternityOwned::get()))]
+		pub fn cancel_sale(origin: OriginFor<T>, pattern_id: T::Hash) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
 
-						let path = pb.finish().unwrap();
+			ensure!(Self::is_pattern_owner(&pattern_id, &buyer)?, <Error<T>>::NotPatternOwner);
+			let escrow = Self::escrow(&pattern_id).ok_or(<Error<T>>::NotInEscrow)?;
+			ensure!(
+				escrow.deadline <= <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::ResealNotOverdue
+			);
+
+			<Escrows<T>>::remove(&pattern_id);
+			T::Currency::unreserve(&buyer, escrow.amount);
+			Self::transfer_pattern_to(&pattern_id, &escrow.seller, Self::sealed_key(&pattern_id))?;
 
-						let mut pixmap = Pixmap::new(20, 20).unwrap();
-						pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
-						pixmap
-					}";
+			Self::deposit_event(Event::SaleCancelled(buyer, escrow.seller, pattern_id));
+
+			Ok(())
+		}
+
+		/// Destroy a pattern owned by the sender. Standing offers on it are released. A pattern
+		/// bought while its payment is held in escrow cannot be burned.
+		#[pallet::weight(T::WeightInfo::burn(T::MaxPatternityOwned::get(), T::MaxOffers::get()))]
+		pub fn burn(origin: OriginFor<T>, pattern_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_pattern_owner(&pattern_id, &sender)?, <Error<T>>::NotPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			ensure!(!<Escrows<T>>::contains_key(&pattern_id), <Error<T>>::PatternInEscrow);
+
+			Self::remove_owned(&sender, &pattern_id)?;
+
+			let pattern = <Patternity<T>>::take(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
+			if let Some(collection_id) = pattern.collection {
+				<CollectionPatterns<T>>::remove(collection_id, &pattern_id);
+			}
+			<SealedKeys<T>>::remove(&pattern_id);
+			<PendingReseal<T>>::remove(&pattern_id);
+			<SeedSalts<T>>::remove(&pattern_id);
+			<Unsalted<T>>::remove(&pattern_id);
+			<ForSale<T>>::remove(&pattern_id);
+			<Approvals<T>>::remove(&pattern_id);
+			if let Some(traits) = <Traits<T>>::take(&pattern_id) {
+				Self::uncount_traits(&traits);
+			}
+			if let Some(metadata) = <Metadata<T>>::take(&pattern_id) {
+				T::Currency::unreserve(&metadata.depositor, metadata.deposit);
+			}
+			for (bidder, offer) in <Offers<T>>::drain_prefix(&pattern_id) {
+				T::Currency::unreserve(&bidder, offer.amount);
+			}
+			<OfferCnt<T>>::remove(&pattern_id);
+
+			<PatternityCnt<T>>::mutate(|cnt| *cnt = cnt.saturating_sub(1));
+
+			Self::deposit_event(Event::Burned(sender, pattern_id));
+
+			Ok(())
+		}
+
+		/// Offer `amount` for a pattern, whether or not it is listed for sale. The amount is
+		/// reserved until the offer is accepted or withdrawn, and replaces any earlier offer of
+		/// the sender on the same pattern. A pattern holds up to `MaxOffers` offers.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			amount: BalanceOf<T>,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
 
-			let size_diff = 2100usize - text_base.len();
+			let pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
+			ensure!(pattern.owner != bidder, <Error<T>>::OfferOnOwnPattern);
+			ensure!(
+				expires_at > <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::OfferExpired
+			);
 
-			for _ in 0..size_diff {
-				text_base = format!("{}{}", text_base, "\x00");
+			match <Offers<T>>::take(&pattern_id, &bidder) {
+				Some(previous) => {
+					T::Currency::unreserve(&bidder, previous.amount);
+				},
+				None => {
+					<OfferCnt<T>>::try_mutate(&pattern_id, |cnt| {
+						ensure!(*cnt < T::MaxOffers::get(), <Error<T>>::TooManyOffers);
+						*cnt += 1;
+						Ok::<_, Error<T>>(())
+					})?;
+				},
 			}
 
-			let mut msg: [u8; 2100] = text_base.as_bytes().to_vec().try_into().unwrap();
+			T::Currency::reserve(&bidder, amount).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+
+			<Offers<T>>::insert(&pattern_id, &bidder, Offer { amount, expires_at });
 
-			Enocoro128::apply_keystream_static(&key, &iv, &mut msg);
+			Self::deposit_event(Event::OfferMade(bidder, pattern_id, amount, expires_at));
 
-			msg
+			Ok(())
 		}
 
-		fn mint(account_id: &T::AccountId, price: Option<BalanceOf<T>>) -> PatternSeed<T> {
-			let key = Self::generate_random_key();
-			let iv = Self::generate_random_iv();
+		/// Withdraw the sender's offer on a pattern and release the reserved amount.
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, pattern_id: T::Hash) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
 
-			let cipher = Self::cipher_code(key, iv);
+			let offer = Self::take_offer(&pattern_id, &bidder).ok_or(<Error<T>>::OfferNotExist)?;
+			T::Currency::unreserve(&bidder, offer.amount);
 
-			let cipher_bv: BoundedVec<u8, T::MaxByteCipher> =
-				BoundedVec::try_from(cipher.to_vec()).unwrap();
+			Self::deposit_event(Event::OfferWithdrawn(bidder, pattern_id));
 
-			PatternSeed::<T> { key, iv, cipher: cipher_bv, owner: account_id.clone(), price }
+			Ok(())
+		}
+
+		/// Remove the expired offer of `bidder` on a pattern and release the reserved amount to
+		/// the bidder. Anyone can prune an expired offer.
+		#[pallet::weight(T::WeightInfo::prune_offer())]
+		pub fn prune_offer(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			bidder: T::AccountId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let offer = Self::offer(&pattern_id, &bidder).ok_or(<Error<T>>::OfferNotExist)?;
+			ensure!(
+				offer.expires_at <= <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::OfferNotExpired
+			);
+
+			Self::take_offer(&pattern_id, &bidder);
+			T::Currency::unreserve(&bidder, offer.amount);
+
+			Self::deposit_event(Event::OfferPruned(bidder, pattern_id));
+
+			Ok(())
+		}
+
+		/// Accept the offer of `bidder` on a pattern owned by the sender, handing over its key
+		/// re-sealed to the bidder.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::accept_offer(T::MaxPatternityOwned::get()))]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			bidder: T::AccountId,
+			sealed_key: SealedKey,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(Self::is_pattern_owner(&pattern_id, &seller)?, <Error<T>>::NotPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
+
+			let pattern = Self::patternity(&pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
+			let offer = Self::take_offer(&pattern_id, &bidder).ok_or(<Error<T>>::OfferNotExist)?;
+			ensure!(
+				offer.expires_at > <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::OfferExpired
+			);
+
+			let to_owned = <PatternityOwned<T>>::get(&bidder);
+			ensure!(
+				(to_owned.len() as u32) < T::MaxPatternityOwned::get(),
+				<Error<T>>::ExceedMaxPatternityOwned
+			);
+
+			Self::pay_for_pattern(&pattern_id, &pattern, &seller, &bidder, offer.amount)?;
+
+			Self::transfer_pattern_to(&pattern_id, &bidder, Some(sealed_key))?;
+
+			Self::deposit_event(Event::OfferAccepted(seller, bidder, pattern_id, offer.amount));
+
+			Ok(())
+		}
+
+		/// Put a pattern owned by the sender up for auction until block `end`. The pattern
+		/// cannot be transferred, priced, sold or burned while the auction is live, and cannot be
+		/// auctioned before its key is re-sealed to the sender.
+		#[pallet::weight(T::WeightInfo::start_auction())]
+		pub fn start_auction(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			reserve_price: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_pattern_owner(&pattern_id, &sender)?, <Error<T>>::NotPatternOwner);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
+			ensure!(end > <frame_system::Pallet<T>>::block_number(), <Error<T>>::InvalidAuctionEnd);
+
+			<AuctionsEnding<T>>::try_mutate(end, |ending| ending.try_push(pattern_id))
+				.map_err(|_| <Error<T>>::TooManyAuctionsEnding)?;
+
+			<Patternity<T>>::mutate(&pattern_id, |pattern| {
+				if let Some(pattern) = pattern {
+					pattern.price = None;
+				}
+			});
+			<ForSale<T>>::remove(&pattern_id);
+
+			<Auctions<T>>::insert(&pattern_id, Auction { reserve_price, end, highest_bid: None });
+
+			Self::deposit_event(Event::AuctionStarted(sender, pattern_id, reserve_price, end));
+
+			Ok(())
+		}
+
+		/// Bid `amount` on a live auction. The amount has to meet the reserve price and beat the
+		/// highest bid, and stays reserved until the sender is outbid or the auction settles.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let mut auction = Self::auction(&pattern_id).ok_or(<Error<T>>::AuctionNotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < auction.end,
+				<Error<T>>::AuctionEnded
+			);
+			ensure!(
+				!Self::is_pattern_owner(&pattern_id, &bidder)?,
+				<Error<T>>::BuyerIsPatternOwner
+			);
+			ensure!(amount >= auction.reserve_price, <Error<T>>::AuctionBidTooLow);
+
+			if let Some((previous_bidder, previous_amount)) = auction.highest_bid.take() {
+				ensure!(amount > previous_amount, <Error<T>>::AuctionBidTooLow);
+				T::Currency::unreserve(&previous_bidder, previous_amount);
+			}
+
+			T::Currency::reserve(&bidder, amount).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+
+			auction.highest_bid = Some((bidder.clone(), amount));
+			<Auctions<T>>::insert(&pattern_id, auction);
+
+			Self::deposit_event(Event::AuctionBid(bidder, pattern_id, amount));
+
+			Ok(())
+		}
+
+		/// Approve `delegate` to transfer a pattern owned by the sender through `transfer_from`,
+		/// or clear the approval with `None`. The approval is cleared when the pattern changes
+		/// hands.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			pattern_id: T::Hash,
+			delegate: Option<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_pattern_owner(&pattern_id, &sender)?, <Error<T>>::NotPatternOwner);
+
+			match &delegate {
+				Some(delegate) => <Approvals<T>>::insert(&pattern_id, delegate),
+				None => <Approvals<T>>::remove(&pattern_id),
+			}
+
+			Self::deposit_event(Event::Approved(sender, pattern_id, delegate));
+
+			Ok(())
+		}
+
+		/// Allow or disallow `operator` to transfer every pattern of the sender through
+		/// `transfer_from`.
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if approved {
+				<Operators<T>>::insert(&sender, &operator, ());
+			} else {
+				<Operators<T>>::remove(&sender, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll(sender, operator, approved));
+
+			Ok(())
+		}
+
+		/// Transfer a pattern owned by `from` on their behalf. The sender has to be the owner, the
+		/// approved delegate of the pattern or an operator of `from`. The sender never holds the
+		/// key: `from` seals it to the recipient off-chain and hands the `sealed_key` over.
+		#[pallet::weight(T::WeightInfo::transfer_from(T::MaxPatternityOwned::get()))]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			pattern_id: T::Hash,
+			sealed_key: SealedKey,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_pattern_owner(&pattern_id, &from)?, <Error<T>>::NotPatternOwner);
+			ensure!(
+				sender == from ||
+					Self::approved(&pattern_id) == Some(sender.clone()) ||
+					Self::is_operator(&from, &sender),
+				<Error<T>>::NotApproved
+			);
+			Self::ensure_not_in_auction(&pattern_id)?;
+			Self::ensure_key_sealed(&pattern_id)?;
+
+			ensure!(from != to, <Error<T>>::TransferToSelf);
+
+			let to_owned = <PatternityOwned<T>>::get(&to);
+			ensure!(
+				(to_owned.len() as u32) < T::MaxPatternityOwned::get(),
+				<Error<T>>::ExceedMaxPatternityOwned
+			);
+
+			Self::transfer_pattern_to(&pattern_id, &to, Some(sealed_key))?;
+
+			Self::deposit_event(Event::Transferred(from, to, pattern_id));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Draws a salt for a pattern id from the chain's randomness source. The random value is
+		/// mixed with the sender, their account nonce and the current pattern count so that
+		/// two mints in the same block never share a salt, while every node importing that
+		/// block derives exactly the same one.
+		fn random_seed(sender: &T::AccountId) -> [u8; 32] {
+			let (random, _) = T::Randomness::random(b"patternities/mint");
+			let nonce = <frame_system::Pallet<T>>::account_nonce(sender);
+
+			(random, sender.clone(), nonce, Self::patternity_cnt()).blake2_256()
+		}
+
+		/// The id of [`BUILTIN_PROGRAM`] in [`GeneratorPrograms`].
+		pub fn builtin_program() -> T::Hash {
+			T::Hashing::hash(BUILTIN_PROGRAM.as_bytes())
+		}
+
+		/// Stores `code` in [`GeneratorPrograms`] unless it is already there, and returns its id.
+		pub(crate) fn register_program(code: &[u8]) -> Result<T::Hash, Error<T>> {
+			let program_id = T::Hashing::hash(code);
+
+			if !<GeneratorPrograms<T>>::contains_key(program_id) {
+				let code: BoundedVec<u8, T::MaxProgramLen> =
+					code.to_vec().try_into().map_err(|_| <Error<T>>::ProgramTooLong)?;
+				<GeneratorPrograms<T>>::insert(program_id, code);
+			}
+
+			Ok(program_id)
+		}
+
+		/// Registers [`BUILTIN_PROGRAM`] as [`BUILTIN_GENERATOR`] unless it already is.
+		pub(crate) fn register_builtin_generator() -> Result<(), Error<T>> {
+			let program = Self::register_program(BUILTIN_PROGRAM.as_bytes())?;
+			if !<Generators<T>>::contains_key(BUILTIN_GENERATOR) {
+				<Generators<T>>::insert(
+					BUILTIN_GENERATOR,
+					Generator { program, kind: ProgramKind::Source, deprecated: false },
+				);
+			}
+
+			Ok(())
+		}
+
+		/// The commitment stored in [`PatternSeed::key_hash`], letting an owner check the key
+		/// they unsealed.
+		pub fn key_hash(key: &[u8; 16], iv: &[u8; 8]) -> [u8; 32] {
+			(*key, *iv).blake2_256()
+		}
+
+		/// The generator seed of a pattern with the given key and [`SeedSalts`] entry.
+		pub fn seed_of(key: &[u8; 16], salt: Option<&[u8; 32]>) -> u64 {
+			match salt {
+				None => seed_from_key(key),
+				Some(salt) => {
+					let mut seed = [0u8; 8];
+					seed.copy_from_slice(&(*key, *salt).blake2_256()[..8]);
+					u64::from_le_bytes(seed)
+				},
+			}
+		}
+
+		/// The generator seed of a stored pattern, once its salt is drawn.
+		fn pattern_seed(pattern_id: &T::Hash, key: &[u8; 16]) -> Result<u64, Error<T>> {
+			ensure!(!<Unsalted<T>>::contains_key(pattern_id), <Error<T>>::SeedNotDrawn);
+			Ok(Self::seed_of(key, Self::seed_salt(pattern_id).as_ref()))
+		}
+
+		/// Draws the salt of every pattern minted in the previous block and returns how many
+		/// were salted. The parent hash is only known once that block is sealed, after the
+		/// minters signed their calls. Each mint pays for its own salt.
+		pub(crate) fn draw_seed_salts() -> u32 {
+			let (random, _) = T::Randomness::random(b"patternities/seed");
+			let parent_hash = <frame_system::Pallet<T>>::parent_hash();
+
+			let mut salted = 0;
+			for (pattern_id, ()) in <Unsalted<T>>::drain() {
+				<SeedSalts<T>>::insert(pattern_id, (random, parent_hash, pattern_id).blake2_256());
+				salted += 1;
+			}
+
+			salted
+		}
+
+		/// The visual traits the generator program draws for `seed`. This replays its random
+		/// number generator, so the values match the rendered image exactly.
+		pub fn traits_of_seed(seed: u64) -> PatternTraits {
+			let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+			let color = [rng.gen_range(0..255), rng.gen_range(0..255), rng.gen_range(0..255)];
+
+			let first = [rng.gen_range(0..10), rng.gen_range(10..25)];
+			let second = [rng.gen_range(25..50), rng.gen_range(50..75)];
+			let third = [rng.gen_range(10..20), rng.gen_range(0..20)];
+
+			PatternTraits { color, vertices: [first, second, third] }
+		}
+
+		pub(crate) fn count_traits(traits: &PatternTraits) {
+			<TraitsCnt<T>>::mutate(|cnt| *cnt = cnt.saturating_add(1));
+			for bucket in traits.buckets() {
+				<TraitBucketCnt<T>>::mutate(bucket, |cnt| *cnt = cnt.saturating_add(1));
+			}
+		}
+
+		fn uncount_traits(traits: &PatternTraits) {
+			<TraitsCnt<T>>::mutate(|cnt| *cnt = cnt.saturating_sub(1));
+			for bucket in traits.buckets() {
+				<TraitBucketCnt<T>>::mutate_exists(bucket, |cnt| {
+					*cnt = cnt.map(|cnt| cnt.saturating_sub(1)).filter(|cnt| *cnt > 0)
+				});
+			}
+		}
+
+		/// The rarity score of a pattern relative to the live patterns with disclosed traits, if
+		/// its own traits were disclosed, see [`Traits`]: the sum over its trait buckets of the
+		/// population divided by the bucket's count, in thousandths. A bucket shared by every
+		/// pattern adds `1000`.
+		pub fn rarity_score(pattern_id: &T::Hash) -> Option<u64> {
+			let traits = Self::pattern_traits(pattern_id)?;
+			let total = u64::from(Self::traits_cnt());
+
+			Some(
+				traits
+					.buckets()
+					.iter()
+					.map(|bucket| {
+						let cnt = u64::from(Self::trait_bucket_cnt(bucket)).max(1);
+						total.saturating_mul(1000) / cnt
+					})
+					.sum(),
+			)
+		}
+
+		fn mint(
+			account_id: &T::AccountId,
+			price: Option<BalanceOf<T>>,
+			key_hash: [u8; 32],
+			royalty: Permill,
+			collection: Option<CollectionId>,
+			generator: Option<GeneratorVersion>,
+		) -> Result<PatternSeed<T>, Error<T>> {
+			ensure!(royalty <= T::MaxRoyalty::get(), <Error<T>>::RoyaltyTooHigh);
+
+			let generator = generator.unwrap_or_else(Self::default_generator);
+			let registered = Self::generator(generator).ok_or(<Error<T>>::GeneratorNotExist)?;
+			ensure!(!registered.deprecated, <Error<T>>::GeneratorIsDeprecated);
+
+			Ok(PatternSeed::<T> {
+				key_hash,
+				generator,
+				owner: account_id.clone(),
+				price,
+				creator: account_id.clone(),
+				royalty,
+				collection,
+			})
+		}
+
+		/// Stores a freshly minted pattern under an id salted with on-chain randomness and
+		/// returns the id. Without a sealed key, the owner is left to seal it through `reseal`,
+		/// and the pattern is only listed for sale from then on.
+		fn insert_pattern(
+			owner: &T::AccountId,
+			pattern_obj: PatternSeed<T>,
+			sealed_key: Option<SealedKey>,
+		) -> Result<T::Hash, Error<T>> {
+			let pattern_id = T::Hashing::hash_of(&(Self::random_seed(owner), &pattern_obj));
+			ensure!(!<Patternity<T>>::contains_key(&pattern_id), <Error<T>>::PatternExists);
+
+			let new_cnt =
+				Self::patternity_cnt().checked_add(1).ok_or(<Error<T>>::PatternCntOverflow)?;
+
+			<PatternityOwned<T>>::try_mutate(owner, |patt_vec| patt_vec.try_push(pattern_id))
+				.map_err(|_| <Error<T>>::ExceedMaxPatternityOwned)?;
+
+			match sealed_key {
+				Some(sealed_key) => {
+					if let Some(price) = pattern_obj.price {
+						<ForSale<T>>::insert(pattern_id, price);
+					}
+					<SealedKeys<T>>::insert(pattern_id, sealed_key);
+				},
+				None => <PendingReseal<T>>::insert(pattern_id, owner.clone()),
+			}
+			<Patternity<T>>::insert(pattern_id, pattern_obj);
+
+			<PatternityCnt<T>>::put(new_cnt);
+
+			Ok(pattern_id)
+		}
+
+		/// The pattern with the given hash, as served by the runtime API.
+		pub fn pattern_info(
+			pattern_id: &T::Hash,
+		) -> Option<PatternInfo<T::AccountId, BalanceOf<T>, T::Hash>> {
+			let pattern = Self::patternity(pattern_id)?;
+			let generator = Self::generator(pattern.generator)?;
+
+			Some(PatternInfo {
+				key_status: Self::key_status(pattern_id)?,
+				owner: pattern.owner,
+				price: pattern.price,
+				key_hash: pattern.key_hash,
+				generator: pattern.generator,
+				program: generator.program,
+				creator: pattern.creator,
+				royalty: pattern.royalty,
+				collection: pattern.collection,
+				seed_salt: Self::seed_salt(pattern_id),
+			})
+		}
+
+		/// Whether the owner of a pattern can unseal its key.
+		pub fn key_status(pattern_id: &T::Hash) -> Option<KeyStatus<T::AccountId>> {
+			if let Some(prev_owner) = Self::pending_reseal(pattern_id) {
+				return Some(KeyStatus::PendingReseal(prev_owner))
+			}
+
+			<SealedKeys<T>>::contains_key(pattern_id).then(|| KeyStatus::Sealed)
+		}
+
+		/// Up to `limit` patterns owned by `owner`, skipping the first `start`.
+		pub fn patterns_owned_page(owner: &T::AccountId, start: u32, limit: u32) -> Vec<T::Hash> {
+			Self::patternity_owned(owner)
+				.into_iter()
+				.skip(start as usize)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// Up to `limit` patterns listed for sale with their price, skipping the first `start`.
+		pub fn patterns_for_sale(start: u32, limit: u32) -> Vec<(T::Hash, BalanceOf<T>)> {
+			<ForSale<T>>::iter().skip(start as usize).take(limit as usize).collect()
+		}
+
+		/// Up to `limit` patterns minted in a collection and not burned, skipping the first
+		/// `start`.
+		pub fn patterns_in_collection(
+			collection_id: CollectionId,
+			start: u32,
+			limit: u32,
+		) -> Vec<T::Hash> {
+			<CollectionPatterns<T>>::iter_key_prefix(collection_id)
+				.skip(start as usize)
+				.take(limit as usize)
+				.collect()
 		}
 
 		pub fn is_pattern_owner(
@@ -425,30 +1682,235 @@ pub mod pallet {
 			}
 		}
 
+		/// Applies `update` to the metadata of a pattern owned by `owner`, then moves the deposit
+		/// for its new size over to `owner`.
+		fn update_metadata(
+			pattern_id: &T::Hash,
+			owner: &T::AccountId,
+			update: impl FnOnce(&mut PatternMetadata<T>) -> Result<(), Error<T>>,
+		) -> DispatchResult {
+			ensure!(Self::is_pattern_owner(pattern_id, owner)?, <Error<T>>::NotPatternOwner);
+
+			let mut metadata = Self::metadata(pattern_id).unwrap_or_else(|| PatternMetadata {
+				name: Default::default(),
+				description: Default::default(),
+				attributes: BoundedBTreeMap::new(),
+				frozen: false,
+				depositor: owner.clone(),
+				deposit: Zero::zero(),
+			});
+			ensure!(!metadata.frozen, <Error<T>>::MetadataIsFrozen);
+
+			update(&mut metadata)?;
+
+			let bytes =
+				(&metadata.name, &metadata.description, &metadata.attributes).encoded_size();
+			let deposit = T::MetadataDepositPerByte::get()
+				.saturating_mul((bytes as u32).into())
+				.saturating_add(T::MetadataDepositBase::get());
+
+			T::Currency::unreserve(&metadata.depositor, metadata.deposit);
+			T::Currency::reserve(owner, deposit).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			metadata.depositor = owner.clone();
+			metadata.deposit = deposit;
+
+			<Metadata<T>>::insert(pattern_id, metadata);
+
+			Ok(())
+		}
+
+		/// Whether `operator` may transfer every pattern of `owner`.
+		pub fn is_operator(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+			<Operators<T>>::contains_key(owner, operator)
+		}
+
+		/// Removes the offer of `bidder` on a pattern, leaving the reserved amount to the caller.
+		fn take_offer(pattern_id: &T::Hash, bidder: &T::AccountId) -> Option<Offer<T>> {
+			let offer = <Offers<T>>::take(pattern_id, bidder)?;
+			<OfferCnt<T>>::mutate_exists(pattern_id, |cnt| {
+				*cnt = cnt.map(|cnt| cnt.saturating_sub(1)).filter(|cnt| *cnt > 0)
+			});
+			Some(offer)
+		}
+
+		fn ensure_not_in_auction(pattern_id: &T::Hash) -> Result<(), Error<T>> {
+			ensure!(!<Auctions<T>>::contains_key(pattern_id), <Error<T>>::PatternInAuction);
+			Ok(())
+		}
+
+		/// A pattern only changes hands while its key is sealed to its owner. Otherwise the next
+		/// owner would be named to re-seal a key they were never given, and the key would be lost.
+		fn ensure_key_sealed(pattern_id: &T::Hash) -> Result<(), Error<T>> {
+			ensure!(!<PendingReseal<T>>::contains_key(pattern_id), <Error<T>>::KeyPendingReseal);
+			Ok(())
+		}
+
+		/// Settles the auctions ending at `now` and returns how many there were.
+		pub(crate) fn end_auctions(now: T::BlockNumber) -> u32 {
+			let ended = <AuctionsEnding<T>>::take(now);
+
+			for pattern_id in ended.iter() {
+				Self::end_auction(pattern_id);
+			}
+
+			ended.len() as u32
+		}
+
+		/// Settles an ended auction. The pattern goes to the highest bidder, whose reserved bid is
+		/// held in escrow until the previous owner re-seals the key through `reseal`. If the
+		/// pattern cannot be handed over, the bid is released instead.
+		fn end_auction(pattern_id: &T::Hash) {
+			let auction = match <Auctions<T>>::take(pattern_id) {
+				Some(auction) => auction,
+				None => return,
+			};
+
+			let winner = match auction.highest_bid {
+				Some((bidder, amount)) =>
+					if Self::settle_auction(pattern_id, &bidder, amount).is_ok() {
+						Some((bidder, amount))
+					} else {
+						T::Currency::unreserve(&bidder, amount);
+						None
+					},
+				None => None,
+			};
+
+			Self::deposit_event(Event::AuctionEnded(*pattern_id, winner));
+		}
+
 		#[transactional]
-		pub fn transfer_pattern_to(patternity_id: &T::Hash, to: &T::AccountId) -> Result<(), Error<T>> {
-			let mut patternity = Self::patternity(&patternity_id).ok_or(<Error<T>>::PatternNotExist)?;
+		fn settle_auction(
+			pattern_id: &T::Hash,
+			bidder: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let pattern = Self::patternity(pattern_id).ok_or(<Error<T>>::PatternNotExist)?;
 
-			let prev_owner = patternity.owner.clone();
+			Self::transfer_pattern_to(pattern_id, bidder, None)?;
+			Self::hold_in_escrow(pattern_id, pattern.owner, amount);
+
+			Ok(())
+		}
+
+		/// The royalty and the marketplace fee due when `seller` sells `pattern` for `amount`.
+		fn cut_of_sale(
+			pattern: &PatternSeed<T>,
+			seller: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> (BalanceOf<T>, BalanceOf<T>) {
+			let fee = T::MarketplaceFee::get().mul_floor(amount);
+			// A reaped creator cannot be paid into, so the royalty stays with the seller.
+			let royalty = if &pattern.creator == seller ||
+				T::Currency::total_balance(&pattern.creator).is_zero()
+			{
+				Zero::zero()
+			} else {
+				pattern.royalty.mul_floor(amount)
+			};
+
+			(royalty, fee)
+		}
+
+		/// Pays `amount` from the reserved balance of `payer` to `seller`, splitting off the
+		/// marketplace fee and the royalty of the creator of `pattern`. Returns the royalty and the
+		/// fee paid.
+		fn pay_for_pattern(
+			pattern_id: &T::Hash,
+			pattern: &PatternSeed<T>,
+			seller: &T::AccountId,
+			payer: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+			let (royalty, fee) = Self::cut_of_sale(pattern, seller, amount);
+
+			Self::pay(payer, &T::FeeDestination::get(), fee)?;
+			Self::pay(payer, &pattern.creator, royalty)?;
+			let proceeds = amount.saturating_sub(fee).saturating_sub(royalty);
+			Self::pay(payer, seller, proceeds)?;
+
+			if !fee.is_zero() {
+				Self::deposit_event(Event::FeeCollected(*pattern_id, fee));
+			}
+
+			Ok((royalty, fee))
+		}
 
-			<PatternityOwned<T>>::try_mutate(&prev_owner, |owned| {
-				if let Some(ind) = owned.iter().position(|&id| id == *patternity_id) {
+		/// Holds the `amount` reserved from the new owner of a pattern sold without its key until
+		/// `seller` re-seals the key to them.
+		fn hold_in_escrow(pattern_id: &T::Hash, seller: T::AccountId, amount: BalanceOf<T>) {
+			let deadline =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::ResealPeriod::get());
+			<Escrows<T>>::insert(pattern_id, Escrow { seller, amount, deadline });
+		}
+
+		/// Pays `amount` from the reserved balance of `from` to the free balance of `to`.
+		fn pay(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			let unpaid = T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Free)?;
+			ensure!(unpaid.is_zero(), <Error<T>>::NotEnoughBalance);
+
+			Ok(())
+		}
+
+		fn remove_owned(owner: &T::AccountId, pattern_id: &T::Hash) -> Result<(), Error<T>> {
+			<PatternityOwned<T>>::try_mutate(owner, |owned| {
+				if let Some(ind) = owned.iter().position(|id| id == pattern_id) {
 					owned.swap_remove(ind);
 					return Ok(());
 				}
 				Err(())
 			})
-			.map_err(|_| <Error<T>>::PatternityOwned)?;
+			.map_err(|_| <Error<T>>::PatternityOwned)
+		}
+
+		/// Moves a pattern to `to`. Without a `sealed_key` for the new owner, the previous owner
+		/// is left to provide one through `reseal`. Callers ensure the key is sealed to the
+		/// previous owner, see [`Self::ensure_key_sealed`].
+		#[transactional]
+		pub fn transfer_pattern_to(
+			patternity_id: &T::Hash,
+			to: &T::AccountId,
+			sealed_key: Option<SealedKey>,
+		) -> Result<(), Error<T>> {
+			let mut patternity = Self::patternity(&patternity_id).ok_or(<Error<T>>::PatternNotExist)?;
+
+			let prev_owner = patternity.owner.clone();
+
+			Self::remove_owned(&prev_owner, patternity_id)?;
 
 			patternity.owner = to.clone();
 
 			patternity.price = None;
 
 			<Patternity<T>>::insert(patternity_id, patternity);
+			<ForSale<T>>::remove(patternity_id);
+			<Approvals<T>>::remove(patternity_id);
+			<Metadata<T>>::mutate(patternity_id, |metadata| {
+				if let Some(metadata) = metadata {
+					T::Currency::unreserve(&metadata.depositor, metadata.deposit);
+					metadata.depositor = to.clone();
+					metadata.deposit = Zero::zero();
+				}
+			});
 
 			<PatternityOwned<T>>::try_mutate(to, |vec| vec.try_push(*patternity_id))
 				.map_err(|_| <Error<T>>::ExceedMaxPatternityOwned)?;
 
+			match sealed_key {
+				Some(sealed_key) => {
+					<SealedKeys<T>>::insert(patternity_id, sealed_key);
+					<PendingReseal<T>>::remove(patternity_id);
+				},
+				// The previous owner's sealed key stays so they can still unseal and reseal it.
+				None => {
+					<PendingReseal<T>>::insert(patternity_id, prev_owner);
+				},
+			}
+
 			Ok(())
 		}
 	}
@@ -0,0 +1,258 @@
+//! Storage migrations of the patternities pallet.
+
+use crate::{Config, Pallet, PatternSeed, Patternity, PendingReseal, Traits, BUILTIN_GENERATOR};
+#[cfg(any(feature = "try-runtime", test))]
+use frame_support::storage::unhashed;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::{PerThing, Permill},
+	traits::{Currency, StorageVersion},
+};
+use patternities_primitives::seed_from_key;
+use sp_std::vec::Vec;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The storage layout before pattern keys were sealed to their owner.
+pub mod v0 {
+	use super::*;
+
+	/// Length of the encrypted generator program every v0 pattern held.
+	pub const CIPHER_LEN: usize = 2100;
+
+	/// A pattern with its key and iv in plaintext.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+	pub struct PatternSeed<T: Config> {
+		pub key: [u8; 16],
+		pub iv: [u8; 8],
+		pub cipher: Vec<u8>,
+		pub price: Option<BalanceOf<T>>,
+		pub owner: T::AccountId,
+	}
+}
+
+/// Migrates patterns from [`v0::PatternSeed`] to the current [`PatternSeed`].
+pub mod v5 {
+	use super::*;
+
+	/// Replaces the plaintext key of every pattern with its commitment. The runtime cannot seal
+	/// the key, so the owner is left to seal it to themselves through `reseal`, and a priced
+	/// pattern is only listed from then on. As the key was public anyway, the traits of the
+	/// pattern are recorded as disclosed.
+	///
+	/// Every cipher was [`crate::BUILTIN_PROGRAM`] encrypted with the key of its pattern, so all of
+	/// them collapse into that program, registered once as [`BUILTIN_GENERATOR`]. Patterns minted
+	/// before had no royalty, no collection and no offers.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 5 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		// Only fails if `MaxProgramLen` is too small for the builtin program, which
+		// `post_migrate` reports.
+		let _ = <Pallet<T>>::register_builtin_generator();
+
+		let mut translated = 0u64;
+
+		<Patternity<T>>::translate::<v0::PatternSeed<T>, _>(|pattern_id, old| {
+			translated += 1;
+
+			let traits = <Pallet<T>>::traits_of_seed(seed_from_key(&old.key));
+			<Traits<T>>::insert(pattern_id, traits);
+			<Pallet<T>>::count_traits(&traits);
+
+			<PendingReseal<T>>::insert(pattern_id, old.owner.clone());
+
+			Some(PatternSeed {
+				key_hash: <Pallet<T>>::key_hash(&old.key, &old.iv),
+				generator: BUILTIN_GENERATOR,
+				price: old.price,
+				owner: old.owner.clone(),
+				creator: old.owner,
+				royalty: Permill::zero(),
+				collection: None,
+			})
+		});
+
+		StorageVersion::new(5).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(3 + 4 * translated, 3 + 6 * translated)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		if StorageVersion::get::<Pallet<T>>() < 5 {
+			let mut patterns = 0u64;
+			for pattern_id in <Patternity<T>>::iter_keys() {
+				let key = <Patternity<T>>::hashed_key_for(pattern_id);
+				ensure!(
+					unhashed::get::<v0::PatternSeed<T>>(&key).is_some(),
+					"A pattern is not in the v0 layout"
+				);
+				patterns += 1;
+			}
+			<Pallet<T>>::set_temp_storage(patterns, "v0_patterns");
+		}
+
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		ensure!(StorageVersion::get::<Pallet<T>>() >= 5, "Storage version was not bumped");
+		ensure!(
+			<crate::Generators<T>>::contains_key(BUILTIN_GENERATOR),
+			"The builtin generator was not registered"
+		);
+
+		let mut patterns = 0u64;
+		for pattern_id in <Patternity<T>>::iter_keys() {
+			ensure!(
+				<Pallet<T>>::key_status(&pattern_id).is_some(),
+				"Pattern has neither a sealed key nor a pending reseal"
+			);
+			patterns += 1;
+		}
+		if let Some(expected) = <Pallet<T>>::get_temp_storage::<u64>("v0_patterns") {
+			ensure!(patterns == expected, "Patterns were lost in the migration");
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, GeneratorPrograms, Generators, KeyStatus, SEALED_KEY_LEN};
+	use frame_support::assert_ok;
+	use sp_core::H256;
+
+	fn put_v0_pattern(pattern_id: H256, owner: u64, key: [u8; 16], iv: [u8; 8]) {
+		let old = v0::PatternSeed::<Test> {
+			key,
+			iv,
+			cipher: vec![key[0]; v0::CIPHER_LEN],
+			price: Some(10),
+			owner,
+		};
+		unhashed::put(&<Patternity<Test>>::hashed_key_for(pattern_id), &old);
+	}
+
+	/// Clears what genesis stores but v0 storage did not have.
+	fn reset_to_v0() {
+		StorageVersion::new(0).put::<ThePatternities>();
+		<Generators<Test>>::remove(BUILTIN_GENERATOR);
+		<GeneratorPrograms<Test>>::remove(ThePatternities::builtin_program());
+	}
+
+	/// The encoded size of all patterns and generator programs.
+	fn state_size() -> usize {
+		let patterns: usize = <Patternity<Test>>::iter_keys()
+			.map(<Patternity<Test>>::hashed_key_for)
+			.filter_map(|key| unhashed::get_raw(&key))
+			.map(|value| value.len())
+			.sum();
+		let programs: usize =
+			<GeneratorPrograms<Test>>::iter_values().map(|code| code.encoded_size()).sum();
+
+		patterns + programs
+	}
+
+	#[test]
+	fn migrates_v0_patterns_losslessly() {
+		new_test_ext().execute_with(|| {
+			reset_to_v0();
+
+			let (key, iv) = ([1; 16], [2; 8]);
+			let pattern_id = H256::repeat_byte(1);
+			put_v0_pattern(pattern_id, ALICE, key, iv);
+			put_v0_pattern(H256::repeat_byte(2), BOB, [3; 16], [4; 8]);
+
+			ThePatternities::on_runtime_upgrade();
+
+			let pattern = ThePatternities::patternity(pattern_id).unwrap();
+			assert_eq!(pattern.key_hash, ThePatternities::key_hash(&key, &iv));
+			assert_eq!(pattern.generator, BUILTIN_GENERATOR);
+			assert_eq!(pattern.price, Some(10));
+			assert_eq!(pattern.owner, ALICE);
+			assert_eq!(pattern.creator, ALICE);
+			assert_eq!(pattern.royalty, Permill::zero());
+			assert_eq!(pattern.collection, None);
+
+			let generator = ThePatternities::generator(BUILTIN_GENERATOR).unwrap();
+			assert_eq!(generator.program, ThePatternities::builtin_program());
+			assert_eq!(generator.kind, crate::ProgramKind::Source);
+			assert!(!generator.deprecated);
+			assert_eq!(ThePatternities::default_generator(), BUILTIN_GENERATOR);
+
+			assert_eq!(
+				ThePatternities::key_status(&pattern_id),
+				Some(KeyStatus::PendingReseal(ALICE))
+			);
+			assert_eq!(
+				ThePatternities::pattern_traits(pattern_id),
+				Some(ThePatternities::traits_of_seed(seed_from_key(&key)))
+			);
+			assert_eq!(ThePatternities::traits_cnt(), 2);
+			assert_eq!(<Patternity<Test>>::iter().count(), 2);
+			assert_eq!(ThePatternities::for_sale(pattern_id), None);
+			assert_eq!(ThePatternities::offer_cnt(pattern_id), 0);
+			assert_eq!(StorageVersion::get::<ThePatternities>(), 5);
+
+			assert_ok!(ThePatternities::reseal(
+				Origin::signed(ALICE),
+				pattern_id,
+				[7; SEALED_KEY_LEN]
+			));
+			assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+			assert_eq!(ThePatternities::for_sale(pattern_id), Some(10));
+		});
+	}
+
+	#[test]
+	fn migrates_v0_ciphers_into_one_program() {
+		new_test_ext().execute_with(|| {
+			const PATTERNS: usize = 10;
+
+			reset_to_v0();
+			for i in 0..PATTERNS {
+				put_v0_pattern(H256::repeat_byte(i as u8), BOB, [i as u8; 16], [0; 8]);
+			}
+			let before = state_size();
+
+			ThePatternities::on_runtime_upgrade();
+
+			assert_eq!(<Patternity<Test>>::iter_keys().count(), PATTERNS);
+			assert_eq!(<GeneratorPrograms<Test>>::iter().count(), 1);
+
+			// Every pattern trades its cipher for a generator version, and the program is stored
+			// once.
+			let pattern_size =
+				ThePatternities::patternity(H256::repeat_byte(3)).unwrap().encoded_size();
+			let program = ThePatternities::builtin_program();
+			let program_size = ThePatternities::generator_program(program).unwrap().encoded_size();
+			let after = state_size();
+			assert_eq!(after, PATTERNS * pattern_size + program_size);
+			assert!(after * 4 < before);
+		});
+	}
+
+	#[test]
+	fn migration_skips_current_storage() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(StorageVersion::get::<ThePatternities>(), 5);
+
+			let pattern_id = mint(ALICE, [1; 16], [2; 8]);
+			let pattern = ThePatternities::patternity(pattern_id);
+
+			ThePatternities::on_runtime_upgrade();
+
+			assert_eq!(ThePatternities::patternity(pattern_id), pattern);
+			assert_eq!(ThePatternities::traits_cnt(), 0);
+		});
+	}
+}
@@ -0,0 +1,143 @@
+use crate as pallet_the_patternities;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Randomness},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	BuildStorage, Permill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const FEE_DESTINATION: u64 = 99;
+
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		ThePatternities: pallet_the_patternities::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Randomness that changes with every block, good enough to salt pattern ids in tests.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
+}
+
+parameter_types! {
+	pub const MaxRoyalty: Permill = Permill::from_percent(25);
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const FeeDestination: u64 = FEE_DESTINATION;
+}
+
+impl pallet_the_patternities::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Randomness = TestRandomness;
+	type MaxPatternityOwned = ConstU32<3>;
+	type MaxProgramLen = ConstU32<10000>;
+	type MaxOffers = ConstU32<2>;
+	type MaxAuctionsEnding = ConstU32<2>;
+	type ResealPeriod = ConstU64<10>;
+	type MaxRoyalty = MaxRoyalty;
+	type MarketplaceFee = MarketplaceFee;
+	type FeeDestination = FeeDestination;
+	type MaxCollectionNameLen = ConstU32<16>;
+	type StringLimit = ConstU32<16>;
+	type MaxAttributes = ConstU32<2>;
+	type MetadataDepositBase = ConstU64<10>;
+	type MetadataDepositPerByte = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		balances: BalancesConfig {
+			balances: vec![
+				(ALICE, INITIAL_BALANCE),
+				(BOB, INITIAL_BALANCE),
+				(CHARLIE, INITIAL_BALANCE),
+				(FEE_DESTINATION, 1),
+			],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Mint a pattern for `owner` from the given key material and return its id.
+pub fn mint(owner: u64, key: [u8; 16], iv: [u8; 8]) -> H256 {
+	assert_ok!(ThePatternities::create_pattern(
+		Origin::signed(owner),
+		None,
+		ThePatternities::key_hash(&key, &iv),
+		[0; pallet_the_patternities::SEALED_KEY_LEN],
+		Permill::zero(),
+		None,
+	));
+	*ThePatternities::patternity_owned(owner).last().unwrap()
+}
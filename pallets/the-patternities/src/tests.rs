@@ -0,0 +1,1211 @@
+use crate::{
+	mock::*, Error, GeneratorPrograms, KeyStatus, PatternTraits, Patternity, PatternityCnt,
+	PatternityOwned, ProgramKind, TraitBucket, BUILTIN_GENERATOR, BUILTIN_PROGRAM, SEALED_KEY_LEN,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	sp_runtime::{traits::BadOrigin, PerThing, Permill},
+	traits::{GetStorageVersion, Hooks, ReservableCurrency, StorageVersion},
+};
+use patternities_primitives::{seed_from_key, showcase_key_material};
+use sp_core::H256;
+use sp_runtime::BuildStorage;
+
+const SEALED: [u8; SEALED_KEY_LEN] = [7; SEALED_KEY_LEN];
+
+fn key(byte: u8) -> ([u8; 16], [u8; 8]) {
+	([byte; 16], [byte; 8])
+}
+
+fn mint_for(owner: u64, byte: u8) -> H256 {
+	let (key, iv) = key(byte);
+	mint(owner, key, iv)
+}
+
+fn list(owner: u64, pattern_id: H256, price: u64) {
+	assert_ok!(ThePatternities::set_price(Origin::signed(owner), pattern_id, Some(price)));
+}
+
+fn last_event() -> crate::Event<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::ThePatternities(event) => Some(event),
+			_ => None,
+		})
+		.last()
+		.expect("an event was deposited")
+}
+
+fn has_event(event: crate::Event<Test>) -> bool {
+	System::events()
+		.iter()
+		.any(|record| record.event == Event::ThePatternities(event.clone()))
+}
+
+/// Every pattern is listed under its owner, every listed pattern is owned by the account it is
+/// listed under, and the pattern count matches.
+fn assert_ownership_consistent() {
+	let mut patterns = 0;
+	for (pattern_id, pattern) in <Patternity<Test>>::iter() {
+		assert!(ThePatternities::patternity_owned(pattern.owner).contains(&pattern_id));
+		patterns += 1;
+	}
+
+	for (owner, owned) in <PatternityOwned<Test>>::iter() {
+		for pattern_id in owned {
+			assert_eq!(ThePatternities::patternity(pattern_id).map(|p| p.owner), Some(owner));
+		}
+	}
+
+	assert_eq!(ThePatternities::patternity_cnt(), patterns);
+}
+
+#[test]
+fn genesis_starts_empty_at_current_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ThePatternities::patternity_cnt(), 0);
+		assert_eq!(<Patternity<Test>>::iter().count(), 0);
+		assert_eq!(
+			StorageVersion::get::<ThePatternities>(),
+			ThePatternities::current_storage_version()
+		);
+	});
+}
+
+#[test]
+fn generator_programs_are_stored_once() {
+	new_test_ext().execute_with(|| {
+		let program = ThePatternities::builtin_program();
+		assert_eq!(
+			ThePatternities::generator_program(program).map(|code| code.into_inner()),
+			Some(BUILTIN_PROGRAM.as_bytes().to_vec())
+		);
+
+		let first = mint_for(ALICE, 1);
+		let second = mint_for(BOB, 2);
+		assert_eq!(ThePatternities::pattern_info(&first).unwrap().program, program);
+		assert_eq!(ThePatternities::pattern_info(&second).unwrap().program, program);
+		assert_eq!(<GeneratorPrograms<Test>>::iter().count(), 1);
+
+		assert_eq!(ThePatternities::register_program(BUILTIN_PROGRAM.as_bytes()), Ok(program));
+		assert_eq!(<GeneratorPrograms<Test>>::iter().count(), 1);
+		assert_eq!(
+			ThePatternities::register_program(&[0; 10_001]),
+			Err(Error::<Test>::ProgramTooLong)
+		);
+	});
+}
+
+#[test]
+fn generators_are_governed_by_root() {
+	new_test_ext().execute_with(|| {
+		let code = b"\0asm".to_vec();
+		assert_noop!(
+			ThePatternities::register_generator(
+				Origin::signed(ALICE),
+				1,
+				ProgramKind::Wasm,
+				code.clone()
+			),
+			BadOrigin
+		);
+
+		assert_ok!(ThePatternities::register_generator(
+			Origin::root(),
+			1,
+			ProgramKind::Wasm,
+			code.clone()
+		));
+		let program = ThePatternities::generator(1).unwrap().program;
+		assert_eq!(ThePatternities::generator_program(program).unwrap().into_inner(), code);
+		assert_eq!(last_event(), crate::Event::GeneratorRegistered(1, program, ProgramKind::Wasm));
+		assert_noop!(
+			ThePatternities::register_generator(Origin::root(), 1, ProgramKind::Wasm, code),
+			Error::<Test>::GeneratorExists
+		);
+
+		let (key, iv) = key(1);
+		let create = |generator| {
+			ThePatternities::create_pattern(
+				Origin::signed(ALICE),
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				SEALED,
+				Permill::zero(),
+				generator,
+			)
+		};
+		assert_noop!(create(Some(7)), Error::<Test>::GeneratorNotExist);
+		assert_ok!(create(Some(1)));
+		let wasm_pattern = ThePatternities::patternity_owned(ALICE)[0];
+		assert_eq!(ThePatternities::patternity(wasm_pattern).unwrap().generator, 1);
+		assert_eq!(ThePatternities::pattern_info(&wasm_pattern).unwrap().program, program);
+
+		assert_noop!(ThePatternities::set_default_generator(Origin::signed(ALICE), 1), BadOrigin);
+		assert_noop!(
+			ThePatternities::set_default_generator(Origin::root(), 7),
+			Error::<Test>::GeneratorNotExist
+		);
+		assert_ok!(ThePatternities::set_default_generator(Origin::root(), 1));
+		assert_eq!(last_event(), crate::Event::DefaultGeneratorSet(1));
+		let pattern_id = mint_for(BOB, 2);
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().generator, 1);
+
+		assert_noop!(
+			ThePatternities::deprecate_generator(Origin::root(), 1),
+			Error::<Test>::GeneratorIsDefault
+		);
+		assert_ok!(ThePatternities::set_default_generator(Origin::root(), BUILTIN_GENERATOR));
+		assert_noop!(ThePatternities::deprecate_generator(Origin::signed(ALICE), 1), BadOrigin);
+		assert_noop!(
+			ThePatternities::deprecate_generator(Origin::root(), 7),
+			Error::<Test>::GeneratorNotExist
+		);
+		assert_ok!(ThePatternities::deprecate_generator(Origin::root(), 1));
+		assert_eq!(last_event(), crate::Event::GeneratorDeprecated(1));
+
+		assert_noop!(create(Some(1)), Error::<Test>::GeneratorIsDeprecated);
+		assert_noop!(
+			ThePatternities::set_default_generator(Origin::root(), 1),
+			Error::<Test>::GeneratorIsDeprecated
+		);
+		assert_eq!(ThePatternities::pattern_info(&wasm_pattern).unwrap().program, program);
+
+		// Traits are those drawn by the builtin generator.
+		System::set_block_number(2);
+		ThePatternities::on_initialize(2);
+		assert!(ThePatternities::seed_salt(wasm_pattern).is_some());
+		assert_noop!(
+			ThePatternities::disclose_traits(Origin::signed(ALICE), wasm_pattern, key, iv),
+			Error::<Test>::TraitsUnknown
+		);
+	});
+}
+
+#[test]
+fn genesis_patterns_are_inserted_and_counted() {
+	let (showcase_key, showcase_iv) = showcase_key_material(2);
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		the_patternities: ThePatternitiesConfig {
+			the_patternities: vec![
+				(ALICE, Some(10), (showcase_key, showcase_iv)),
+				(ALICE, None, showcase_key_material(9)),
+				(BOB, Some(20), showcase_key_material(212)),
+			],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+
+	ext.execute_with(|| {
+		assert_eq!(ThePatternities::patternity_cnt(), 3);
+		assert_eq!(ThePatternities::patternity_owned(ALICE).len(), 2);
+		assert_eq!(ThePatternities::patternity_owned(BOB).len(), 1);
+		assert_ownership_consistent();
+
+		// The key seeds its pattern as is.
+		let showcase = ThePatternities::patternity_owned(ALICE)[0];
+		let pattern = ThePatternities::patternity(showcase).unwrap();
+		assert_eq!(pattern.price, Some(10));
+		assert_eq!(pattern.key_hash, ThePatternities::key_hash(&showcase_key, &showcase_iv));
+		assert_eq!(ThePatternities::seed_salt(showcase), None);
+		assert_eq!(
+			ThePatternities::pattern_traits(showcase),
+			Some(ThePatternities::traits_of_seed(2))
+		);
+		assert_eq!(ThePatternities::traits_cnt(), 3);
+		let bobs = ThePatternities::patternity_owned(BOB)[0];
+
+		for pattern_id in <Patternity<Test>>::iter_keys() {
+			let owner = ThePatternities::patternity(pattern_id).unwrap().owner;
+			assert_eq!(
+				ThePatternities::key_status(&pattern_id),
+				Some(KeyStatus::PendingReseal(owner))
+			);
+		}
+
+		// Patterns are only listed once their key is sealed.
+		assert_eq!(ThePatternities::patterns_for_sale(0, 10), vec![]);
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(CHARLIE), bobs, 20),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_ok!(ThePatternities::reseal(Origin::signed(BOB), bobs, SEALED));
+		assert_eq!(ThePatternities::patterns_for_sale(0, 10), vec![(bobs, 20)]);
+	});
+}
+
+#[test]
+fn create_pattern_works() {
+	new_test_ext().execute_with(|| {
+		let (key, iv) = key(1);
+		let pattern_id = mint(ALICE, key, iv);
+
+		let pattern = ThePatternities::patternity(pattern_id).unwrap();
+		assert_eq!(pattern.owner, ALICE);
+		assert_eq!(pattern.creator, ALICE);
+		assert_eq!(pattern.key_hash, ThePatternities::key_hash(&key, &iv));
+		assert_eq!(pattern.generator, BUILTIN_GENERATOR);
+		assert_eq!(
+			ThePatternities::pattern_info(&pattern_id).unwrap().program,
+			ThePatternities::builtin_program()
+		);
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some([0; SEALED_KEY_LEN]));
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+		assert_eq!(last_event(), crate::Event::Created(ALICE, pattern_id));
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn create_pattern_rejects_invalid_input() {
+	new_test_ext().execute_with(|| {
+		let (key, iv) = key(1);
+		assert_noop!(
+			ThePatternities::create_pattern(
+				Origin::signed(ALICE),
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				SEALED,
+				Permill::from_percent(26),
+				None,
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+	});
+}
+
+#[test]
+fn create_pattern_respects_limits() {
+	new_test_ext().execute_with(|| {
+		for byte in 1..=3 {
+			mint_for(ALICE, byte);
+		}
+		let (key, iv) = key(4);
+		assert_noop!(
+			ThePatternities::create_pattern(
+				Origin::signed(ALICE),
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				SEALED,
+				Permill::zero(),
+				None,
+			),
+			Error::<Test>::ExceedMaxPatternityOwned
+		);
+
+		<PatternityCnt<Test>>::put(u64::MAX);
+		assert_noop!(
+			ThePatternities::create_pattern(
+				Origin::signed(BOB),
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				SEALED,
+				Permill::zero(),
+				None,
+			),
+			Error::<Test>::PatternCntOverflow
+		);
+	});
+}
+
+#[test]
+fn create_pattern_rejects_duplicate_id() {
+	new_test_ext().execute_with(|| {
+		mint_for(ALICE, 1);
+
+		// The same minter, nonce, count and pattern in the same block hash to the same id.
+		<PatternityCnt<Test>>::put(0);
+		let (key, iv) = key(1);
+		assert_noop!(
+			ThePatternities::create_pattern(
+				Origin::signed(ALICE),
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				[0; SEALED_KEY_LEN],
+				Permill::zero(),
+				None,
+			),
+			Error::<Test>::PatternExists
+		);
+	});
+}
+
+#[test]
+fn set_price_works() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+
+		list(ALICE, pattern_id, 100);
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().price, Some(100));
+		assert_eq!(last_event(), crate::Event::PriceSet(ALICE, pattern_id, Some(100)));
+
+		assert_noop!(
+			ThePatternities::set_price(Origin::signed(BOB), pattern_id, None),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::set_price(Origin::signed(ALICE), H256::repeat_byte(9), None),
+			Error::<Test>::PatternNotExist
+		);
+	});
+}
+
+#[test]
+fn listings_follow_the_price() {
+	new_test_ext().execute_with(|| {
+		let (sold, auctioned, burned, unlisted) =
+			(mint_for(ALICE, 1), mint_for(ALICE, 2), mint_for(ALICE, 3), mint_for(ALICE, 4));
+		for pattern_id in [sold, auctioned, burned, unlisted] {
+			list(ALICE, pattern_id, 100);
+		}
+		assert_ok!(ThePatternities::set_price(Origin::signed(ALICE), unlisted, None));
+		assert_eq!(ThePatternities::for_sale(unlisted), None);
+		assert_eq!(ThePatternities::patterns_for_sale(0, 10).len(), 3);
+
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), sold, 100));
+		assert_ok!(ThePatternities::start_auction(Origin::signed(ALICE), auctioned, 100, 5));
+		assert_ok!(ThePatternities::burn(Origin::signed(ALICE), burned));
+
+		assert_eq!(ThePatternities::patterns_for_sale(0, 10), vec![]);
+	});
+}
+
+#[test]
+fn transfer_works() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		list(ALICE, pattern_id, 100);
+
+		assert_ok!(ThePatternities::transfer(Origin::signed(ALICE), BOB, pattern_id, SEALED));
+
+		let pattern = ThePatternities::patternity(pattern_id).unwrap();
+		assert_eq!(pattern.owner, BOB);
+		assert_eq!(pattern.price, None);
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some(SEALED));
+		assert_eq!(last_event(), crate::Event::Transferred(ALICE, BOB, pattern_id));
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn transfer_fails_for_invalid_transfers() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(BOB), CHARLIE, pattern_id, SEALED),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(ALICE), ALICE, pattern_id, SEALED),
+			Error::<Test>::TransferToSelf
+		);
+
+		for byte in 2..=4 {
+			mint_for(BOB, byte);
+		}
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(ALICE), BOB, pattern_id, SEALED),
+			Error::<Test>::ExceedMaxPatternityOwned
+		);
+
+		<PatternityOwned<Test>>::remove(ALICE);
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(ALICE), CHARLIE, pattern_id, SEALED),
+			Error::<Test>::PatternityOwned
+		);
+	});
+}
+
+#[test]
+fn buy_pattern_pays_seller_fee_and_royalty() {
+	new_test_ext().execute_with(|| {
+		let (key, iv) = key(1);
+		assert_ok!(ThePatternities::create_pattern(
+			Origin::signed(ALICE),
+			Some(1_000),
+			ThePatternities::key_hash(&key, &iv),
+			SEALED,
+			Permill::from_percent(10),
+			None,
+		));
+		let pattern_id = ThePatternities::patternity_owned(ALICE)[0];
+
+		// The creator sells first, so no royalty is due.
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 1_000));
+		assert_eq!(last_event(), crate::Event::Bought(BOB, ALICE, pattern_id, 1_000, 0, 20));
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::PendingReseal(ALICE)));
+		// The seller's sealed key stays until the reseal, so they can still unseal it.
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some(SEALED));
+
+		// The payment is held until the seller re-seals the key.
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::reserved_balance(BOB), 1_000);
+		assert_eq!(ThePatternities::escrow(pattern_id).unwrap().seller, ALICE);
+		assert!(!has_event(crate::Event::FeeCollected(pattern_id, 20)));
+
+		assert_ok!(ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 980);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 1_000);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(FEE_DESTINATION), 1 + 20);
+		assert!(has_event(crate::Event::FeeCollected(pattern_id, 20)));
+		assert_eq!(ThePatternities::escrow(pattern_id), None);
+
+		list(BOB, pattern_id, 1_000);
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(CHARLIE), pattern_id, 1_000));
+		assert_eq!(last_event(), crate::Event::Bought(CHARLIE, BOB, pattern_id, 1_000, 100, 20));
+		assert_ok!(ThePatternities::reseal(Origin::signed(BOB), pattern_id, SEALED));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 980 + 100);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 1_000 + 880);
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn royalty_is_skipped_for_a_reaped_creator() {
+	new_test_ext().execute_with(|| {
+		let (key, iv) = key(1);
+		assert_ok!(ThePatternities::create_pattern(
+			Origin::signed(ALICE),
+			Some(1_000),
+			ThePatternities::key_hash(&key, &iv),
+			SEALED,
+			Permill::from_percent(10),
+			None,
+		));
+		let pattern_id = ThePatternities::patternity_owned(ALICE)[0];
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 1_000));
+		assert_ok!(ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED));
+
+		assert_ok!(Balances::transfer_all(Origin::signed(ALICE), CHARLIE, false));
+		assert!(!System::account_exists(&ALICE));
+
+		list(BOB, pattern_id, 1_000);
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(CHARLIE), pattern_id, 1_000));
+		assert_eq!(last_event(), crate::Event::Bought(CHARLIE, BOB, pattern_id, 1_000, 0, 20));
+		assert_ok!(ThePatternities::reseal(Origin::signed(BOB), pattern_id, SEALED));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 1_000 + 980);
+		assert_eq!(Balances::free_balance(ALICE), 0);
+
+		// An offer accepted from the reaped creator's pattern settles the same way.
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 500, 10));
+		assert_ok!(ThePatternities::accept_offer(Origin::signed(CHARLIE), pattern_id, BOB, SEALED));
+		assert_eq!(Balances::free_balance(CHARLIE), 2 * INITIAL_BALANCE + 980 - 1_000 + 490);
+		assert!(!System::account_exists(&ALICE));
+	});
+}
+
+#[test]
+fn buy_pattern_fails_for_invalid_purchases() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 100),
+			Error::<Test>::PatternNotForSale
+		);
+
+		list(ALICE, pattern_id, 100);
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(ALICE), pattern_id, 100),
+			Error::<Test>::BuyerIsPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 99),
+			Error::<Test>::PatternBidPriceTooLow
+		);
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, INITIAL_BALANCE + 1),
+			Error::<Test>::NotEnoughBalance
+		);
+		assert_noop!(
+			ThePatternities::buy_pattern(Origin::signed(BOB), H256::repeat_byte(9), 100),
+			Error::<Test>::PatternNotExist
+		);
+	});
+}
+
+#[test]
+fn reseal_hands_the_key_over() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		list(ALICE, pattern_id, 100);
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 100));
+
+		assert_noop!(
+			ThePatternities::reseal(Origin::signed(BOB), pattern_id, SEALED),
+			Error::<Test>::NotPendingReseal
+		);
+
+		assert_ok!(ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED));
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some(SEALED));
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+		assert_eq!(last_event(), crate::Event::Resealed(ALICE, pattern_id));
+	});
+}
+
+#[test]
+fn overdue_sales_can_be_cancelled_by_the_buyer() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		list(ALICE, pattern_id, 100);
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 100));
+		let deadline = ThePatternities::escrow(pattern_id).unwrap().deadline;
+		assert_eq!(deadline, System::block_number() + 10);
+
+		assert_noop!(
+			ThePatternities::burn(Origin::signed(BOB), pattern_id),
+			Error::<Test>::PatternInEscrow
+		);
+
+		System::set_block_number(deadline - 1);
+		assert_noop!(
+			ThePatternities::cancel_sale(Origin::signed(BOB), pattern_id),
+			Error::<Test>::ResealNotOverdue
+		);
+
+		System::set_block_number(deadline);
+		assert_noop!(
+			ThePatternities::cancel_sale(Origin::signed(ALICE), pattern_id),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_ok!(ThePatternities::cancel_sale(Origin::signed(BOB), pattern_id));
+		assert_eq!(last_event(), crate::Event::SaleCancelled(BOB, ALICE, pattern_id));
+
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().owner, ALICE);
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some(SEALED));
+		assert_eq!(ThePatternities::escrow(pattern_id), None);
+		assert_noop!(
+			ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED),
+			Error::<Test>::NotPendingReseal
+		);
+		assert_noop!(
+			ThePatternities::cancel_sale(Origin::signed(ALICE), pattern_id),
+			Error::<Test>::NotInEscrow
+		);
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn patterns_pending_reseal_cannot_change_hands() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		list(ALICE, pattern_id, 100);
+		assert_ok!(ThePatternities::buy_pattern(Origin::signed(BOB), pattern_id, 100));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(CHARLIE), pattern_id, 100, 5));
+		assert_ok!(ThePatternities::set_approval_for_all(Origin::signed(BOB), CHARLIE, true));
+
+		assert_noop!(
+			ThePatternities::set_price(Origin::signed(BOB), pattern_id, Some(100)),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(BOB), CHARLIE, pattern_id, SEALED),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_noop!(
+			ThePatternities::transfer_from(
+				Origin::signed(CHARLIE),
+				BOB,
+				CHARLIE,
+				pattern_id,
+				SEALED
+			),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_noop!(
+			ThePatternities::accept_offer(Origin::signed(BOB), pattern_id, CHARLIE, SEALED),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_noop!(
+			ThePatternities::start_auction(Origin::signed(BOB), pattern_id, 100, 5),
+			Error::<Test>::KeyPendingReseal
+		);
+		assert_eq!(ThePatternities::pending_reseal(pattern_id), Some(ALICE));
+
+		assert_ok!(ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED));
+		assert_ok!(ThePatternities::accept_offer(Origin::signed(BOB), pattern_id, CHARLIE, SEALED));
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().owner, CHARLIE);
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+	});
+}
+
+#[test]
+fn burn_removes_the_pattern() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		assert_ok!(ThePatternities::set_metadata(
+			Origin::signed(ALICE),
+			pattern_id,
+			b"name".to_vec(),
+			vec![]
+		));
+
+		assert_noop!(
+			ThePatternities::burn(Origin::signed(BOB), pattern_id),
+			Error::<Test>::NotPatternOwner
+		);
+
+		assert_ok!(ThePatternities::burn(Origin::signed(ALICE), pattern_id));
+		assert_eq!(ThePatternities::patternity(pattern_id), None);
+		assert_eq!(ThePatternities::sealed_key(pattern_id), None);
+		assert_eq!(ThePatternities::metadata(pattern_id), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(last_event(), crate::Event::Burned(ALICE, pattern_id));
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn burn_releases_standing_offers() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		let other = mint_for(ALICE, 2);
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 10));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(CHARLIE), pattern_id, 200, 10));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), other, 50, 10));
+		assert_eq!(ThePatternities::offer_cnt(pattern_id), 2);
+
+		assert_ok!(ThePatternities::burn(Origin::signed(ALICE), pattern_id));
+
+		assert_eq!(ThePatternities::offer(pattern_id, BOB), None);
+		assert_eq!(ThePatternities::offer(pattern_id, CHARLIE), None);
+		assert_eq!(ThePatternities::offer_cnt(pattern_id), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 50);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+		assert_eq!(ThePatternities::offer(other, BOB).unwrap().amount, 50);
+	});
+}
+
+#[test]
+fn offers_are_limited_per_pattern() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 10));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(CHARLIE), pattern_id, 100, 10));
+		assert_noop!(
+			ThePatternities::make_offer(Origin::signed(FEE_DESTINATION), pattern_id, 1, 10),
+			Error::<Test>::TooManyOffers
+		);
+
+		// Replacing an offer does not take another slot, and withdrawing one frees it.
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 200, 10));
+		assert_ok!(ThePatternities::withdraw_offer(Origin::signed(CHARLIE), pattern_id));
+		assert_eq!(ThePatternities::offer_cnt(pattern_id), 1);
+	});
+}
+
+#[test]
+fn offers_can_be_made_withdrawn_and_accepted() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+
+		assert_noop!(
+			ThePatternities::make_offer(Origin::signed(ALICE), pattern_id, 100, 10),
+			Error::<Test>::OfferOnOwnPattern
+		);
+		assert_noop!(
+			ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 1),
+			Error::<Test>::OfferExpired
+		);
+		assert_noop!(
+			ThePatternities::make_offer(Origin::signed(BOB), pattern_id, INITIAL_BALANCE + 1, 10),
+			Error::<Test>::NotEnoughBalance
+		);
+		assert_noop!(
+			ThePatternities::withdraw_offer(Origin::signed(BOB), pattern_id),
+			Error::<Test>::OfferNotExist
+		);
+
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 10));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(last_event(), crate::Event::OfferMade(BOB, pattern_id, 100, 10));
+
+		assert_ok!(ThePatternities::withdraw_offer(Origin::signed(BOB), pattern_id));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(last_event(), crate::Event::OfferWithdrawn(BOB, pattern_id));
+
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 500, 10));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(CHARLIE), pattern_id, 200, 5));
+
+		assert_noop!(
+			ThePatternities::accept_offer(Origin::signed(BOB), pattern_id, BOB, SEALED),
+			Error::<Test>::NotPatternOwner
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			ThePatternities::accept_offer(Origin::signed(ALICE), pattern_id, CHARLIE, SEALED),
+			Error::<Test>::OfferExpired
+		);
+
+		assert_ok!(ThePatternities::accept_offer(Origin::signed(ALICE), pattern_id, BOB, SEALED));
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().owner, BOB);
+		assert_eq!(ThePatternities::sealed_key(pattern_id), Some(SEALED));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 500);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 490);
+		assert_eq!(last_event(), crate::Event::OfferAccepted(ALICE, BOB, pattern_id, 500));
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn expired_offers_can_be_pruned_by_anyone() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 5));
+
+		assert_noop!(
+			ThePatternities::prune_offer(Origin::signed(CHARLIE), pattern_id, BOB),
+			Error::<Test>::OfferNotExpired
+		);
+		assert_noop!(
+			ThePatternities::prune_offer(Origin::signed(CHARLIE), pattern_id, CHARLIE),
+			Error::<Test>::OfferNotExist
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ThePatternities::prune_offer(Origin::signed(CHARLIE), pattern_id, BOB));
+		assert_eq!(ThePatternities::offer(pattern_id, BOB), None);
+		assert_eq!(ThePatternities::offer_cnt(pattern_id), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(last_event(), crate::Event::OfferPruned(BOB, pattern_id));
+	});
+}
+
+#[test]
+fn auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		list(ALICE, pattern_id, 100);
+
+		assert_noop!(
+			ThePatternities::start_auction(Origin::signed(BOB), pattern_id, 100, 5),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::start_auction(Origin::signed(ALICE), pattern_id, 100, 1),
+			Error::<Test>::InvalidAuctionEnd
+		);
+
+		assert_ok!(ThePatternities::start_auction(Origin::signed(ALICE), pattern_id, 100, 5));
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().price, None);
+		assert_eq!(last_event(), crate::Event::AuctionStarted(ALICE, pattern_id, 100, 5));
+
+		assert_noop!(
+			ThePatternities::set_price(Origin::signed(ALICE), pattern_id, Some(100)),
+			Error::<Test>::PatternInAuction
+		);
+		assert_noop!(
+			ThePatternities::transfer(Origin::signed(ALICE), BOB, pattern_id, SEALED),
+			Error::<Test>::PatternInAuction
+		);
+		assert_noop!(
+			ThePatternities::start_auction(Origin::signed(ALICE), pattern_id, 100, 6),
+			Error::<Test>::PatternInAuction
+		);
+
+		assert_noop!(
+			ThePatternities::bid(Origin::signed(ALICE), pattern_id, 100),
+			Error::<Test>::BuyerIsPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::bid(Origin::signed(BOB), pattern_id, 99),
+			Error::<Test>::AuctionBidTooLow
+		);
+
+		assert_ok!(ThePatternities::bid(Origin::signed(BOB), pattern_id, 100));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_noop!(
+			ThePatternities::bid(Origin::signed(CHARLIE), pattern_id, 100),
+			Error::<Test>::AuctionBidTooLow
+		);
+
+		assert_ok!(ThePatternities::bid(Origin::signed(CHARLIE), pattern_id, 150));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 150);
+		assert_eq!(last_event(), crate::Event::AuctionBid(CHARLIE, pattern_id, 150));
+
+		System::set_block_number(5);
+		assert_noop!(
+			ThePatternities::bid(Origin::signed(BOB), pattern_id, 200),
+			Error::<Test>::AuctionEnded
+		);
+
+		ThePatternities::on_initialize(5);
+
+		assert_eq!(ThePatternities::auction(pattern_id), None);
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().owner, CHARLIE);
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::PendingReseal(ALICE)));
+		assert_eq!(last_event(), crate::Event::AuctionEnded(pattern_id, Some((CHARLIE, 150))));
+		// The winning bid stays reserved until the seller re-seals the key.
+		assert_eq!(Balances::reserved_balance(CHARLIE), 150);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+
+		assert_ok!(ThePatternities::reseal(Origin::signed(ALICE), pattern_id, SEALED));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 150);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 147);
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn auction_without_bids_keeps_the_pattern() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		assert_ok!(ThePatternities::start_auction(Origin::signed(ALICE), pattern_id, 100, 5));
+
+		ThePatternities::on_initialize(5);
+
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().owner, ALICE);
+		assert_eq!(ThePatternities::key_status(&pattern_id), Some(KeyStatus::Sealed));
+		assert_eq!(last_event(), crate::Event::AuctionEnded(pattern_id, None));
+		assert_ok!(ThePatternities::set_price(Origin::signed(ALICE), pattern_id, Some(100)));
+	});
+}
+
+#[test]
+fn auctions_are_limited_per_block() {
+	new_test_ext().execute_with(|| {
+		let patterns: Vec<_> = (1..=3).map(|byte| mint_for(ALICE, byte)).collect();
+
+		assert_ok!(ThePatternities::start_auction(Origin::signed(ALICE), patterns[0], 1, 5));
+		assert_ok!(ThePatternities::start_auction(Origin::signed(ALICE), patterns[1], 1, 5));
+		assert_noop!(
+			ThePatternities::start_auction(Origin::signed(ALICE), patterns[2], 1, 5),
+			Error::<Test>::TooManyAuctionsEnding
+		);
+		assert_noop!(
+			ThePatternities::bid(Origin::signed(BOB), patterns[2], 1),
+			Error::<Test>::AuctionNotExist
+		);
+	});
+}
+
+#[test]
+fn approved_delegates_and_operators_can_transfer() {
+	new_test_ext().execute_with(|| {
+		let first = mint_for(ALICE, 1);
+		let second = mint_for(ALICE, 2);
+
+		assert_noop!(
+			ThePatternities::transfer_from(Origin::signed(BOB), ALICE, CHARLIE, first, SEALED),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			ThePatternities::approve(Origin::signed(BOB), first, Some(BOB)),
+			Error::<Test>::NotPatternOwner
+		);
+
+		assert_ok!(ThePatternities::approve(Origin::signed(ALICE), first, Some(BOB)));
+		assert_eq!(last_event(), crate::Event::Approved(ALICE, first, Some(BOB)));
+		assert_noop!(
+			ThePatternities::transfer_from(Origin::signed(BOB), ALICE, CHARLIE, second, SEALED),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(ThePatternities::transfer_from(
+			Origin::signed(BOB),
+			ALICE,
+			CHARLIE,
+			first,
+			SEALED
+		));
+		assert_eq!(ThePatternities::patternity(first).unwrap().owner, CHARLIE);
+		assert_eq!(ThePatternities::approved(first), None);
+		assert_eq!(ThePatternities::key_status(&first), Some(KeyStatus::Sealed));
+		assert_eq!(ThePatternities::sealed_key(first), Some(SEALED));
+		list(CHARLIE, first, 100);
+
+		assert_ok!(ThePatternities::set_approval_for_all(Origin::signed(ALICE), BOB, true));
+		assert_eq!(last_event(), crate::Event::ApprovalForAll(ALICE, BOB, true));
+		assert_ok!(ThePatternities::transfer_from(
+			Origin::signed(BOB),
+			ALICE,
+			CHARLIE,
+			second,
+			SEALED
+		));
+		assert_eq!(ThePatternities::patternity(second).unwrap().owner, CHARLIE);
+
+		assert_ok!(ThePatternities::set_approval_for_all(Origin::signed(ALICE), BOB, false));
+		assert!(!ThePatternities::is_operator(&ALICE, &BOB));
+		assert_ownership_consistent();
+	});
+}
+
+#[test]
+fn collections_enforce_supply_and_mint_price() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ThePatternities::create_collection(Origin::signed(ALICE), vec![b'a'; 17], None, 0),
+			Error::<Test>::CollectionNameTooLong
+		);
+
+		assert_ok!(ThePatternities::create_collection(
+			Origin::signed(ALICE),
+			b"series".to_vec(),
+			Some(1),
+			50
+		));
+		assert_eq!(last_event(), crate::Event::CollectionCreated(ALICE, 0));
+
+		let (key, iv) = key(1);
+		let mint_in = |who: u64, collection_id: u32| {
+			ThePatternities::create_pattern_in_collection(
+				Origin::signed(who),
+				collection_id,
+				None,
+				ThePatternities::key_hash(&key, &iv),
+				SEALED,
+				Permill::zero(),
+				None,
+			)
+		};
+
+		assert_noop!(mint_in(BOB, 1), Error::<Test>::CollectionNotExist);
+
+		assert_ok!(mint_in(BOB, 0));
+		let pattern_id = ThePatternities::patternity_owned(BOB)[0];
+		assert_eq!(ThePatternities::patternity(pattern_id).unwrap().collection, Some(0));
+		assert_eq!(ThePatternities::patterns_in_collection(0, 0, 10), vec![pattern_id]);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 50);
+		assert_eq!(last_event(), crate::Event::CreatedInCollection(BOB, 0, pattern_id));
+
+		assert_noop!(mint_in(CHARLIE, 0), Error::<Test>::CollectionSoldOut);
+
+		assert_ok!(ThePatternities::burn(Origin::signed(BOB), pattern_id));
+		assert!(ThePatternities::patterns_in_collection(0, 0, 10).is_empty());
+		assert_noop!(mint_in(CHARLIE, 0), Error::<Test>::CollectionSoldOut);
+
+		crate::NextCollectionId::<Test>::put(u32::MAX);
+		assert_noop!(
+			ThePatternities::create_collection(Origin::signed(ALICE), vec![], None, 0),
+			Error::<Test>::CollectionCntOverflow
+		);
+	});
+}
+
+#[test]
+fn metadata_reserves_deposit_and_can_be_frozen() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+
+		assert_noop!(
+			ThePatternities::set_metadata(Origin::signed(BOB), pattern_id, vec![], vec![]),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::set_metadata(Origin::signed(ALICE), pattern_id, vec![0; 17], vec![]),
+			Error::<Test>::StringTooLong
+		);
+
+		assert_ok!(ThePatternities::set_metadata(
+			Origin::signed(ALICE),
+			pattern_id,
+			b"name".to_vec(),
+			b"about".to_vec()
+		));
+		assert_eq!(last_event(), crate::Event::MetadataSet(pattern_id));
+		// Base deposit plus the encoded name, description and empty attribute map.
+		assert_eq!(Balances::reserved_balance(ALICE), 10 + 5 + 6 + 1);
+
+		assert_ok!(ThePatternities::set_attribute(
+			Origin::signed(ALICE),
+			pattern_id,
+			b"a".to_vec(),
+			Some(b"1".to_vec())
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::AttributeSet(pattern_id, b"a".to_vec(), Some(b"1".to_vec()))
+		);
+		assert_ok!(ThePatternities::set_attribute(
+			Origin::signed(ALICE),
+			pattern_id,
+			b"b".to_vec(),
+			Some(b"2".to_vec())
+		));
+		assert_noop!(
+			ThePatternities::set_attribute(
+				Origin::signed(ALICE),
+				pattern_id,
+				b"c".to_vec(),
+				Some(b"3".to_vec())
+			),
+			Error::<Test>::TooManyAttributes
+		);
+		assert_ok!(ThePatternities::set_attribute(
+			Origin::signed(ALICE),
+			pattern_id,
+			b"b".to_vec(),
+			None
+		));
+		assert_eq!(ThePatternities::metadata(pattern_id).unwrap().attributes.len(), 1);
+
+		assert_ok!(ThePatternities::freeze_metadata(Origin::signed(ALICE), pattern_id));
+		assert_eq!(last_event(), crate::Event::MetadataFrozen(pattern_id));
+
+		// The deposit is returned to the previous owner, even if the metadata is frozen.
+		assert_ok!(ThePatternities::transfer(Origin::signed(ALICE), BOB, pattern_id, SEALED));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(ThePatternities::metadata(pattern_id).unwrap().deposit, 0);
+		assert_noop!(
+			ThePatternities::set_metadata(Origin::signed(BOB), pattern_id, vec![], vec![]),
+			Error::<Test>::MetadataIsFrozen
+		);
+	});
+}
+
+#[test]
+fn disclosed_traits_are_counted_and_scored() {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		the_patternities: ThePatternitiesConfig {
+			the_patternities: vec![
+				(ALICE, None, showcase_key_material(2)),
+				(ALICE, None, showcase_key_material(9)),
+				(BOB, None, showcase_key_material(212)),
+			],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let owned = ThePatternities::patternity_owned(ALICE);
+		let (first, second) = (owned[0], owned[1]);
+		let third = ThePatternities::patternity_owned(BOB)[0];
+
+		// Seeds 2 and 9 share a colour band, and seeds 2 and 212 an area class.
+		assert_eq!(ThePatternities::traits_cnt(), 3);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::ColorBand(19)), 2);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::ColorBand(11)), 1);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::AreaClass(2)), 2);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::AreaClass(3)), 1);
+		assert_eq!(ThePatternities::rarity_score(&first), Some(3_000));
+		assert_eq!(ThePatternities::rarity_score(&second), Some(4_500));
+		assert_eq!(ThePatternities::rarity_score(&third), Some(4_500));
+
+		// A minted pattern is only counted once its owner discloses its key.
+		let (key, iv) = key(1);
+		let minted = mint(BOB, key, iv);
+		assert_eq!(ThePatternities::pattern_traits(minted), None);
+		assert_eq!(ThePatternities::rarity_score(&minted), None);
+		assert_noop!(
+			ThePatternities::disclose_traits(Origin::signed(ALICE), minted, key, iv),
+			Error::<Test>::NotPatternOwner
+		);
+		assert_noop!(
+			ThePatternities::disclose_traits(Origin::signed(BOB), minted, key, [0; 8]),
+			Error::<Test>::InvalidKey
+		);
+		assert_noop!(
+			ThePatternities::disclose_traits(Origin::signed(BOB), minted, key, iv),
+			Error::<Test>::SeedNotDrawn
+		);
+
+		System::set_block_number(2);
+		ThePatternities::on_initialize(2);
+		assert_eq!(ThePatternities::traits_cnt(), 3);
+
+		assert_ok!(ThePatternities::disclose_traits(Origin::signed(BOB), minted, key, iv));
+		let salt = ThePatternities::seed_salt(minted);
+		let traits = ThePatternities::traits_of_seed(ThePatternities::seed_of(&key, salt.as_ref()));
+		assert_eq!(ThePatternities::pattern_traits(minted), Some(traits));
+		assert_eq!(last_event(), crate::Event::TraitsDisclosed(minted, traits));
+		assert_noop!(
+			ThePatternities::disclose_traits(Origin::signed(BOB), minted, key, iv),
+			Error::<Test>::TraitsAlreadyDisclosed
+		);
+		assert_eq!(ThePatternities::traits_cnt(), 4);
+		for bucket in traits.buckets() {
+			let genesis_cnt = match bucket {
+				TraitBucket::ColorBand(19) | TraitBucket::AreaClass(2) => 2,
+				TraitBucket::ColorBand(11) | TraitBucket::AreaClass(3) => 1,
+				_ => 0,
+			};
+			assert_eq!(ThePatternities::trait_bucket_cnt(bucket), genesis_cnt + 1);
+		}
+
+		assert_ok!(ThePatternities::burn(Origin::signed(BOB), minted));
+		assert_eq!(ThePatternities::traits_cnt(), 3);
+		assert_eq!(ThePatternities::rarity_score(&first), Some(3_000));
+
+		assert_ok!(ThePatternities::burn(Origin::signed(ALICE), second));
+
+		assert_eq!(ThePatternities::traits_cnt(), 2);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::ColorBand(19)), 1);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::ColorBand(11)), 1);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::AreaClass(2)), 2);
+		assert_eq!(ThePatternities::trait_bucket_cnt(TraitBucket::AreaClass(3)), 0);
+		assert_eq!(ThePatternities::rarity_score(&first), Some(3_000));
+		assert_eq!(ThePatternities::rarity_score(&second), None);
+		assert_eq!(ThePatternities::rarity_score(&third), Some(3_000));
+	});
+}
+
+#[test]
+fn seeds_are_salted_in_the_next_block() {
+	new_test_ext().execute_with(|| {
+		let (key, iv) = key(1);
+		let first = mint(ALICE, key, iv);
+		assert_eq!(ThePatternities::seed_salt(first), None);
+
+		System::set_block_number(2);
+		ThePatternities::on_initialize(2);
+		let second = mint(BOB, key, iv);
+
+		System::set_block_number(3);
+		ThePatternities::on_initialize(3);
+
+		// Minting a known key again does not reproduce the pattern.
+		let seed_of = |pattern_id| {
+			ThePatternities::seed_of(&key, ThePatternities::seed_salt(pattern_id).as_ref())
+		};
+		assert_ne!(seed_of(first), seed_of(second));
+		assert_ne!(seed_of(first), seed_from_key(&key));
+		assert!(ThePatternities::pattern_info(&first).unwrap().seed_salt.is_some());
+
+		assert_ok!(ThePatternities::burn(Origin::signed(ALICE), first));
+		assert_eq!(ThePatternities::seed_salt(first), None);
+	});
+}
+
+#[test]
+fn traits_match_the_generator() {
+	// The triangle drawn in images/2-pattern.png.
+	assert_eq!(
+		crate::Pallet::<Test>::traits_of_seed(2),
+		PatternTraits { color: [83, 18, 250], vertices: [[3, 19], [42, 69], [15, 14]] }
+	);
+}
+
+#[test]
+fn replacing_an_offer_releases_the_previous_one() {
+	new_test_ext().execute_with(|| {
+		let pattern_id = mint_for(ALICE, 1);
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 100, 10));
+		assert_ok!(ThePatternities::make_offer(Origin::signed(BOB), pattern_id, 300, 10));
+		assert_eq!(Balances::reserved_balance(BOB), 300);
+		assert_eq!(ThePatternities::offer(pattern_id, BOB).unwrap().amount, 300);
+	});
+}
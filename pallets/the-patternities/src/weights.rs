@@ -0,0 +1,682 @@
+//! Weights for pallet_the_patternities
+//!
+//! Laid out as the benchmark CLI writes them from `.maintain/frame-weight-template.hbs`. Each
+//! call is charged for its storage accesses, counted along the path its benchmark in
+//! `benchmarking.rs` takes, and for its execution time with a slope in `s`, the number of
+//! patterns the account already owns, `p`, the length of a generator program, and the other
+//! components of its benchmark. The execution times are estimates until the benchmarks are run
+//! on reference hardware, which overwrites this file:
+//!
+//! ./target/release/node-patternities-node benchmark
+//! --chain=dev
+//! --execution=wasm
+//! --wasm-execution=compiled
+//! --pallet=pallet_the_patternities
+//! --extrinsic=*
+//! --steps=50
+//! --repeat=20
+//! --template=./.maintain/frame-weight-template.hbs
+//! --output=pallets/the-patternities/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_the_patternities.
+pub trait WeightInfo {
+	fn create_pattern(s: u32, ) -> Weight;
+	fn create_collection() -> Weight;
+	fn create_pattern_in_collection(s: u32, ) -> Weight;
+	fn set_metadata() -> Weight;
+	fn set_attribute() -> Weight;
+	fn freeze_metadata() -> Weight;
+	fn disclose_traits() -> Weight;
+	fn register_generator(p: u32, ) -> Weight;
+	fn deprecate_generator() -> Weight;
+	fn set_default_generator() -> Weight;
+	fn set_price(s: u32, ) -> Weight;
+	fn transfer(s: u32, ) -> Weight;
+	fn buy_pattern(s: u32, ) -> Weight;
+	fn reseal() -> Weight;
+	fn cancel_sale(s: u32, ) -> Weight;
+	fn burn(s: u32, o: u32, ) -> Weight;
+	fn make_offer() -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn prune_offer() -> Weight;
+	fn accept_offer(s: u32, ) -> Weight;
+	fn start_auction() -> Weight;
+	fn bid() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from(s: u32, ) -> Weight;
+	fn end_auctions(a: u32, ) -> Weight;
+	fn draw_seed_salts(s: u32, ) -> Weight;
+}
+
+/// Weights for pallet_the_patternities using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn create_pattern(s: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ThePatternities NextCollectionId (r:1 w:1)
+	// Storage: ThePatternities Collections (r:0 w:1)
+	fn create_collection() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Collections (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities CollectionPatterns (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn create_pattern_in_collection(s: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_metadata() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_attribute() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn freeze_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Traits (r:1 w:1)
+	// Storage: ThePatternities Unsalted (r:1 w:0)
+	// Storage: ThePatternities SeedSalts (r:1 w:0)
+	// Storage: ThePatternities TraitsCnt (r:1 w:1)
+	// Storage: ThePatternities TraitBucketCnt (r:2 w:2)
+	fn disclose_traits() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: ThePatternities Generators (r:1 w:1)
+	// Storage: ThePatternities GeneratorPrograms (r:1 w:1)
+	fn register_generator(p: u32, ) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:1)
+	fn deprecate_generator() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:0 w:1)
+	fn set_default_generator() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:0)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:0)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn set_price(s: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((95_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn transfer(s: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Escrows (r:0 w:1)
+	fn buy_pattern(s: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Escrows (r:1 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn reseal() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Escrows (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities SealedKeys (r:1 w:1)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn cancel_sale(s: u32, ) -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities Escrows (r:1 w:0)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities CollectionPatterns (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities SeedSalts (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities Traits (r:1 w:1)
+	// Storage: ThePatternities TraitsCnt (r:1 w:1)
+	// Storage: ThePatternities TraitBucketCnt (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:0 w:1)
+	// Storage: ThePatternities Offers (r:o w:o)
+	// Storage: System Account (r:o w:o)
+	fn burn(s: u32, o: u32, ) -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((21_000_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn make_offer() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_offer() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn prune_offer() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn accept_offer(s: u32, ) -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities PendingReseal (r:1 w:0)
+	// Storage: ThePatternities AuctionsEnding (r:1 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn start_auction() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Operators (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:1 w:1)
+	// Storage: ThePatternities Operators (r:1 w:0)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn transfer_from(s: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities AuctionsEnding (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities Escrows (r:0 w:1)
+	fn end_auctions(a: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((61_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: ThePatternities Unsalted (r:1 w:1)
+	// Storage: ThePatternities SeedSalts (r:0 w:1)
+	fn draw_seed_salts(s: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn create_pattern(s: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ThePatternities NextCollectionId (r:1 w:1)
+	// Storage: ThePatternities Collections (r:0 w:1)
+	fn create_collection() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Collections (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities CollectionPatterns (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn create_pattern_in_collection(s: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_metadata() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_attribute() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn freeze_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Traits (r:1 w:1)
+	// Storage: ThePatternities Unsalted (r:1 w:0)
+	// Storage: ThePatternities SeedSalts (r:1 w:0)
+	// Storage: ThePatternities TraitsCnt (r:1 w:1)
+	// Storage: ThePatternities TraitBucketCnt (r:2 w:2)
+	fn disclose_traits() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: ThePatternities Generators (r:1 w:1)
+	// Storage: ThePatternities GeneratorPrograms (r:1 w:1)
+	fn register_generator(p: u32, ) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities DefaultGenerator (r:1 w:0)
+	// Storage: ThePatternities Generators (r:1 w:1)
+	fn deprecate_generator() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Generators (r:1 w:0)
+	// Storage: ThePatternities DefaultGenerator (r:0 w:1)
+	fn set_default_generator() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:1 w:0)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:0)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn set_price(s: u32, ) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((95_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn transfer(s: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Escrows (r:0 w:1)
+	fn buy_pattern(s: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Escrows (r:1 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn reseal() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Escrows (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities SealedKeys (r:1 w:1)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn cancel_sale(s: u32, ) -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities Escrows (r:1 w:0)
+	// Storage: ThePatternities PatternityOwned (r:1 w:1)
+	// Storage: ThePatternities CollectionPatterns (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities SeedSalts (r:0 w:1)
+	// Storage: ThePatternities Unsalted (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities Traits (r:1 w:1)
+	// Storage: ThePatternities TraitsCnt (r:1 w:1)
+	// Storage: ThePatternities TraitBucketCnt (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ThePatternities PatternityCnt (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:0 w:1)
+	// Storage: ThePatternities Offers (r:o w:o)
+	// Storage: System Account (r:o w:o)
+	fn burn(s: u32, o: u32, ) -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((21_000_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn make_offer() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_offer() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn prune_offer() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities Offers (r:1 w:1)
+	// Storage: ThePatternities OfferCnt (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn accept_offer(s: u32, ) -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities PendingReseal (r:1 w:0)
+	// Storage: ThePatternities AuctionsEnding (r:1 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn start_auction() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:0)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Operators (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities Approvals (r:1 w:1)
+	// Storage: ThePatternities Operators (r:1 w:0)
+	// Storage: ThePatternities Auctions (r:1 w:0)
+	// Storage: ThePatternities PendingReseal (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities SealedKeys (r:0 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	fn transfer_from(s: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ThePatternities AuctionsEnding (r:1 w:1)
+	// Storage: ThePatternities Auctions (r:1 w:1)
+	// Storage: ThePatternities Patternity (r:1 w:1)
+	// Storage: ThePatternities PatternityOwned (r:2 w:2)
+	// Storage: ThePatternities Metadata (r:1 w:1)
+	// Storage: ThePatternities ForSale (r:0 w:1)
+	// Storage: ThePatternities Approvals (r:0 w:1)
+	// Storage: ThePatternities PendingReseal (r:0 w:1)
+	// Storage: ThePatternities Escrows (r:0 w:1)
+	fn end_auctions(a: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((61_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: ThePatternities Unsalted (r:1 w:1)
+	// Storage: ThePatternities SeedSalts (r:0 w:1)
+	fn draw_seed_salts(s: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+	}
+}
@@ -0,0 +1,21 @@
+//! Definitions shared by the patternities pallet and the native renderer, so both derive the
+//! same seed from the same key.
+
+#![no_std]
+
+/// The generator seed of an unsalted pattern: the first 8 bytes of its key, little-endian.
+pub fn seed_from_key(key: &[u8; 16]) -> u64 {
+	let mut seed = [0u8; 8];
+	seed.copy_from_slice(&key[..8]);
+	u64::from_le_bytes(seed)
+}
+
+/// Key material of a showcase pattern drawn from `seed`: a key holding the seed's little-endian
+/// bytes followed by zeros, and a zero iv, so that [`seed_from_key`] gives back `seed`. Genesis
+/// patterns minted from it are unsalted and render as `images/{seed}-pattern.png`. The key is
+/// public, which only suits showcases.
+pub fn showcase_key_material(seed: u64) -> ([u8; 16], [u8; 8]) {
+	let mut key = [0u8; 16];
+	key[..8].copy_from_slice(&seed.to_le_bytes());
+	(key, [0u8; 8])
+}
@@ -0,0 +1,35 @@
+//! With the `build-wasm` feature, builds this crate for `wasm32-unknown-unknown`, the way it is
+//! registered on chain, and points the conformance tests at the module with `GENERATOR_WASM`.
+
+use std::{env, path::PathBuf, process::Command};
+
+/// Set by cargo for the `build-wasm` feature, and inherited by the nested build unless removed.
+const BUILD_WASM: &str = "CARGO_FEATURE_BUILD_WASM";
+
+fn main() {
+	println!("cargo:rerun-if-changed=src");
+	println!("cargo:rerun-if-changed=../src");
+
+	if env::var_os(BUILD_WASM).is_none() {
+		return
+	}
+
+	let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("Cargo.toml");
+	let target_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("target");
+	let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+
+	let status = Command::new(cargo)
+		.args(["build", "--release", "--target", "wasm32-unknown-unknown", "--manifest-path"])
+		.arg(&manifest)
+		.arg("--target-dir")
+		.arg(&target_dir)
+		.env_remove(BUILD_WASM)
+		.env("CARGO_PROFILE_RELEASE_PANIC", "abort")
+		.env_remove("CARGO_ENCODED_RUSTFLAGS")
+		.status()
+		.expect("cargo can be run");
+	assert!(status.success(), "Failed to build the generator for wasm32-unknown-unknown");
+
+	let module = target_dir.join("wasm32-unknown-unknown/release/patternities_generator.wasm");
+	println!("cargo:rustc-env=GENERATOR_WASM={}", module.display());
+}
@@ -0,0 +1,23 @@
+//! The builtin generator as a WASM generator program.
+//!
+//! Built for `wasm32-unknown-unknown`, this module can be registered on chain with
+//! `register_generator(version, ProgramKind::Wasm, code)` and is run by
+//! `patternities_render::wasm`.
+
+use patternities_render::{render_with, to_rgba, RenderParams};
+use std::ptr;
+
+/// Render the pattern of `seed` and return the offset of its RGBA pixels, or 0 if it cannot be
+/// rendered at `width` by `height`: patterns are square. The pixels are leaked, as every render
+/// runs in a fresh instance.
+#[no_mangle]
+pub extern "C" fn render(seed: u64, width: u32, height: u32) -> *const u8 {
+	if width != height {
+		return ptr::null()
+	}
+
+	match render_with(seed, &RenderParams::with_canvas_size(width)) {
+		Ok(pixmap) => Box::leak(to_rgba(&pixmap).into_boxed_slice()).as_ptr(),
+		Err(_) => ptr::null(),
+	}
+}
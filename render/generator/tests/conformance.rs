@@ -0,0 +1,57 @@
+//! The generator compiled to WASM and run by the node's executor must render the golden
+//! images, like the native renderer does. Run with `--features build-wasm`, which needs the
+//! `wasm32-unknown-unknown` target.
+
+#![cfg(feature = "build-wasm")]
+
+use patternities_render::{from_rgba, wasm, CANVAS_SIZE};
+use std::{fs, path::PathBuf};
+use tiny_skia::Pixmap;
+
+fn module() -> Vec<u8> {
+	fs::read(env!("GENERATOR_WASM")).expect("the generator module was built")
+}
+
+fn golden(seed: u64) -> Pixmap {
+	let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("../../images")
+		.join(format!("{}-pattern.png", seed));
+	Pixmap::load_png(&path).expect("golden image exists")
+}
+
+#[test]
+fn wasm_generator_matches_golden_images() {
+	let code = module();
+
+	for seed in [2, 212, 2242, 9922, 13446, 224221131242] {
+		let expected = golden(seed);
+		let rgba = wasm::execute(&code, seed, CANVAS_SIZE, CANVAS_SIZE).unwrap();
+		// The module returns unpremultiplied colours, as stored in the golden PNGs.
+		let rendered = from_rgba(CANVAS_SIZE, CANVAS_SIZE, &rgba).unwrap();
+
+		let differing = rendered.data().iter().zip(expected.data()).filter(|(a, b)| a != b).count();
+		assert_eq!(differing, 0, "seed {} differs in {} channels", seed, differing);
+	}
+}
+
+#[test]
+fn wasm_generator_is_deterministic() {
+	let code = module();
+
+	assert_eq!(
+		wasm::render_png(&code, 9922, CANVAS_SIZE).unwrap(),
+		wasm::render_png(&code, 9922, CANVAS_SIZE).unwrap()
+	);
+}
+
+#[test]
+fn wasm_generator_renders_thumbnails() {
+	let thumbnail = wasm::render(&module(), 212, 100).unwrap();
+
+	assert_eq!((thumbnail.width(), thumbnail.height()), (100, 100));
+}
+
+#[test]
+fn wasm_generator_refuses_other_shapes() {
+	assert!(wasm::execute(&module(), 212, CANVAS_SIZE / 2, CANVAS_SIZE).is_err());
+}
@@ -0,0 +1,301 @@
+//! Native renderer for patternities.
+//!
+//! This is the tiny-skia pipeline of the generator program embedded in the pallet, so the node
+//! and any tooling can render a pattern without compiling the decrypted source. The output is
+//! pixel-identical to the generator's for the same seed.
+//!
+//! Patterns can also be rendered as SVG, where the triangle tile, its repeat and the circular clip
+//! become a `<pattern>` and a `<clipPath>`, for prints at any size.
+//!
+//! With the `wasm` feature, [`wasm`] also runs generators registered as WASM modules.
+
+pub use patternities_primitives::seed_from_key;
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use std::{fmt, str::FromStr};
+use tiny_skia::{
+	ColorU8, FillRule, FilterQuality, Paint, PathBuilder, Pattern, Pixmap, SpreadMode, Transform,
+};
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Default width and height of a rendered pattern, in pixels. Patterns are laid out on a canvas
+/// of this size and scaled to the canvas of a render.
+pub const CANVAS_SIZE: u32 = 400;
+
+/// Default width and height of the triangle tile, in pixels.
+pub const TILE_SIZE: u32 = 20;
+
+const CIRCLE_RADIUS: f32 = 180.0;
+
+/// Parameters of a render. The defaults render the pattern exactly as the generator does, and
+/// other canvas sizes scale it, so every size of a pattern looks the same.
+#[derive(Clone, Copy, Debug)]
+pub struct RenderParams {
+	/// Width and height of the canvas, in pixels.
+	pub canvas_size: u32,
+	/// Width and height the triangle tile is drawn at, in pixels. The tile covers the same area
+	/// at any size, but larger tiles keep the triangle sharp on large canvases.
+	pub tile_size: u32,
+	/// How the tile is laid over a `CANVAS_SIZE` canvas.
+	pub transform: Transform,
+	/// Whether edges are anti-aliased.
+	pub anti_alias: bool,
+	/// How the tile is sampled when it is transformed.
+	pub filter_quality: FilterQuality,
+}
+
+impl Default for RenderParams {
+	fn default() -> Self {
+		RenderParams {
+			canvas_size: CANVAS_SIZE,
+			tile_size: TILE_SIZE,
+			transform: Transform::from_row(1.5, -0.4, 0.0, -0.8, 5.0, 1.0),
+			anti_alias: true,
+			filter_quality: FilterQuality::Bicubic,
+		}
+	}
+}
+
+impl RenderParams {
+	/// The default parameters on a `canvas_size` canvas.
+	pub fn with_canvas_size(canvas_size: u32) -> Self {
+		RenderParams { canvas_size, ..Default::default() }
+	}
+
+	/// How much the canvas is scaled from `CANVAS_SIZE`.
+	fn canvas_scale(&self) -> f32 {
+		self.canvas_size as f32 / CANVAS_SIZE as f32
+	}
+
+	/// How much the tile is scaled from `TILE_SIZE`.
+	fn tile_scale(&self) -> f32 {
+		self.tile_size as f32 / TILE_SIZE as f32
+	}
+}
+
+/// Image formats a pattern can be rendered to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+	/// A PNG, as the generator writes it.
+	Png,
+	/// A resolution-independent SVG of the same pattern.
+	Svg,
+}
+
+impl FromStr for Format {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"png" => Ok(Format::Png),
+			"svg" => Ok(Format::Svg),
+			_ => Err(format!("Unknown format `{}`, expected `png` or `svg`", s)),
+		}
+	}
+}
+
+/// Errors that can occur while rendering a pattern.
+#[derive(Debug)]
+pub enum Error {
+	/// The canvas or tile could not be allocated.
+	Canvas,
+	/// The rendered image could not be encoded.
+	Encoding(String),
+	/// A generator program failed to render the pattern.
+	Generator(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Canvas => write!(f, "Failed to allocate the canvas"),
+			Error::Encoding(e) => write!(f, "Failed to encode the pattern: {}", e),
+			Error::Generator(e) => write!(f, "The generator failed: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// Render the pattern of `seed`.
+pub fn render(seed: u64) -> Result<Pixmap, Error> {
+	render_with(seed, &RenderParams::default())
+}
+
+/// Render the pattern of `seed` with `params`.
+pub fn render_with(seed: u64, params: &RenderParams) -> Result<Pixmap, Error> {
+	let triangle = triangle(seed, params)?;
+
+	let (tile_scale, canvas_scale) = (params.tile_scale(), params.canvas_scale());
+	let paint = Paint {
+		anti_alias: params.anti_alias,
+		shader: Pattern::new(
+			triangle.as_ref(),
+			SpreadMode::Repeat,
+			params.filter_quality,
+			1.0,
+			params
+				.transform
+				.pre_scale(1.0 / tile_scale, 1.0 / tile_scale)
+				.post_scale(canvas_scale, canvas_scale),
+		),
+		..Default::default()
+	};
+
+	let center = params.canvas_size as f32 / 2.0;
+	let path = PathBuilder::from_circle(center, center, CIRCLE_RADIUS * canvas_scale)
+		.ok_or(Error::Canvas)?;
+
+	let mut pixmap = Pixmap::new(params.canvas_size, params.canvas_size).ok_or(Error::Canvas)?;
+	pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+	Ok(pixmap)
+}
+
+/// Render the pattern of `seed` as PNG bytes.
+pub fn render_png(seed: u64) -> Result<Vec<u8>, Error> {
+	render_as(seed, Format::Png, &RenderParams::default())
+}
+
+/// Render the pattern of `seed` as an SVG document.
+pub fn render_svg(seed: u64) -> String {
+	svg(seed, &RenderParams::default())
+}
+
+/// Render the pattern of `seed` in `format` with `params`. The tile size and filter quality only
+/// apply to PNG.
+pub fn render_as(seed: u64, format: Format, params: &RenderParams) -> Result<Vec<u8>, Error> {
+	match format {
+		Format::Png => render_with(seed, params)?
+			.encode_png()
+			.map_err(|e| Error::Encoding(e.to_string())),
+		Format::Svg => Ok(svg(seed, params).into_bytes()),
+	}
+}
+
+/// The pixels of `pixmap` as RGBA, the image format of generator programs.
+pub fn to_rgba(pixmap: &Pixmap) -> Vec<u8> {
+	pixmap
+		.pixels()
+		.iter()
+		.flat_map(|pixel| {
+			let color = pixel.demultiply();
+			[color.red(), color.green(), color.blue(), color.alpha()]
+		})
+		.collect()
+}
+
+/// The inverse of [`to_rgba`].
+pub fn from_rgba(width: u32, height: u32, rgba: &[u8]) -> Result<Pixmap, Error> {
+	let mut pixmap = Pixmap::new(width, height).ok_or(Error::Canvas)?;
+	if rgba.len() != pixmap.data().len() {
+		return Err(Error::Canvas)
+	}
+
+	for (pixel, color) in pixmap.pixels_mut().iter_mut().zip(rgba.chunks_exact(4)) {
+		*pixel = ColorU8::from_rgba(color[0], color[1], color[2], color[3]).premultiply();
+	}
+
+	Ok(pixmap)
+}
+
+/// The triangle drawn into the tile of a pattern.
+struct Triangle {
+	color: [u8; 3],
+	points: [(u8, u8); 3],
+}
+
+impl Triangle {
+	fn new(seed: u64) -> Self {
+		let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+		let r: u8 = rng.gen_range(0..255);
+		let g: u8 = rng.gen_range(0..255);
+		let b: u8 = rng.gen_range(0..255);
+
+		let start: u8 = rng.gen_range(0..10);
+		let end: u8 = rng.gen_range(10..25);
+
+		let start_second: u8 = rng.gen_range(25..50);
+		let end_second: u8 = rng.gen_range(50..75);
+
+		let start_third: u8 = rng.gen_range(10..20);
+		let end_third: u8 = rng.gen_range(0..20);
+
+		Triangle {
+			color: [r, g, b],
+			points: [(start, end), (start_second, end_second), (start_third, end_third)],
+		}
+	}
+}
+
+/// Lays the pattern out on a `CANVAS_SIZE` view box, scaled to the canvas.
+fn svg(seed: u64, params: &RenderParams) -> String {
+	let Triangle { color: [r, g, b], points: [(x1, y1), (x2, y2), (x3, y3)] } = Triangle::new(seed);
+	let Transform { sx, ky, kx, sy, tx, ty } = params.transform;
+	let center = CANVAS_SIZE as f32 / 2.0;
+	let rendering = if params.anti_alias { "auto" } else { "crispEdges" };
+
+	format!(
+		concat!(
+			r#"<svg xmlns="http://www.w3.org/2000/svg" width="{canvas}" height="{canvas}" "#,
+			r#"viewBox="0 0 {size} {size}" shape-rendering="{rendering}">"#,
+			"<defs>",
+			r#"<pattern id="triangle" patternUnits="userSpaceOnUse" width="{tile}" "#,
+			r#"height="{tile}" patternTransform="matrix({} {} {} {} {} {})">"#,
+			r##"<path d="M{} {} L{} {} L{} {} Z" fill="#{:02x}{:02x}{:02x}"/>"##,
+			"</pattern>",
+			r#"<clipPath id="circle">"#,
+			r#"<circle cx="{center}" cy="{center}" r="{radius}"/>"#,
+			"</clipPath>",
+			"</defs>",
+			r#"<rect width="{size}" height="{size}" fill="url(#triangle)" "#,
+			r#"clip-path="url(#circle)"/>"#,
+			"</svg>\n",
+		),
+		sx,
+		ky,
+		kx,
+		sy,
+		tx,
+		ty,
+		x1,
+		y1,
+		x2,
+		y2,
+		x3,
+		y3,
+		r,
+		g,
+		b,
+		canvas = params.canvas_size,
+		size = CANVAS_SIZE,
+		rendering = rendering,
+		tile = TILE_SIZE,
+		center = center,
+		radius = CIRCLE_RADIUS,
+	)
+}
+
+fn triangle(seed: u64, params: &RenderParams) -> Result<Pixmap, Error> {
+	let Triangle { color: [r, g, b], points } = Triangle::new(seed);
+
+	let mut paint = Paint { anti_alias: params.anti_alias, ..Default::default() };
+	paint.set_color_rgba8(r, g, b, 255);
+
+	let mut pb = PathBuilder::new();
+	pb.move_to(points[0].0 as f32, points[0].1 as f32);
+	for (x, y) in &points[1..] {
+		pb.line_to(*x as f32, *y as f32);
+	}
+
+	let path = pb.finish().ok_or(Error::Canvas)?;
+
+	let scale = params.tile_scale();
+	let mut pixmap = Pixmap::new(params.tile_size, params.tile_size).ok_or(Error::Canvas)?;
+	pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::from_scale(scale, scale), None);
+
+	Ok(pixmap)
+}
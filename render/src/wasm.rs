@@ -0,0 +1,110 @@
+//! Executor for generators registered as WASM modules.
+//!
+//! Generator modules may not import anything, so they run without any access to the host. Their
+//! memory is capped at [`MAX_MEMORY_PAGES`] and they are metered with wasmi's fuel, so a render
+//! that uses up its budget traps instead of running on.
+
+use crate::{from_rgba, Error};
+use std::fmt;
+use tiny_skia::Pixmap;
+use wasmi::{Config, Engine, Linker, Module, StoreLimits, StoreLimitsBuilder};
+
+/// Largest width or height a generator is asked to render.
+pub const MAX_CANVAS_SIZE: u32 = 4096;
+
+/// Most 64 KiB pages of memory a generator can grow to: room for a few canvases of
+/// [`MAX_CANVAS_SIZE`].
+pub const MAX_MEMORY_PAGES: u32 = 4096;
+
+/// Fuel a generator is given whatever the canvas, enough to grow its memory to
+/// [`MAX_MEMORY_PAGES`]: wasmi charges one unit of fuel per instruction and per 64 bytes of
+/// memory grown, filled or copied.
+pub const BASE_FUEL: u64 = 10_000_000;
+
+/// Fuel a generator is given on top of [`BASE_FUEL`] for each pixel of the canvas it renders.
+pub const FUEL_PER_PIXEL: u64 = 10_000;
+
+const PAGE_SIZE: usize = 64 * 1024;
+
+type Store = wasmi::Store<StoreLimits>;
+
+/// Run the generator module `code` for `seed`, returning the RGBA image it rendered.
+pub fn execute(code: &[u8], seed: u64, width: u32, height: u32) -> Result<Vec<u8>, Error> {
+	let fuel = BASE_FUEL + FUEL_PER_PIXEL * width as u64 * height as u64;
+	execute_with_fuel(code, seed, width, height, fuel)
+}
+
+/// Like [`execute`], trapping once the generator has used up `fuel`.
+pub fn execute_with_fuel(
+	code: &[u8],
+	seed: u64,
+	width: u32,
+	height: u32,
+	fuel: u64,
+) -> Result<Vec<u8>, Error> {
+	if width == 0 || height == 0 || width > MAX_CANVAS_SIZE || height > MAX_CANVAS_SIZE {
+		return Err(Error::Canvas)
+	}
+
+	let mut config = Config::default();
+	config.consume_fuel(true);
+	let engine = Engine::new(&config);
+	let module = Module::new(&engine, code).map_err(generator_error)?;
+
+	let limits = StoreLimitsBuilder::new()
+		.memory_size(MAX_MEMORY_PAGES as usize * PAGE_SIZE)
+		.build();
+	let mut store = Store::new(&engine, limits);
+	store.limiter(|limits| limits);
+	store.add_fuel(fuel).map_err(generator_error)?;
+
+	run(&mut store, &module, seed, width, height)
+}
+
+fn run(
+	store: &mut Store,
+	module: &Module,
+	seed: u64,
+	width: u32,
+	height: u32,
+) -> Result<Vec<u8>, Error> {
+	let instance = Linker::new(store.engine())
+		.instantiate(&mut *store, module)
+		.and_then(|instance| instance.start(&mut *store))
+		.map_err(generator_error)?;
+
+	let render = instance
+		.get_typed_func::<(i64, i32, i32), i32>(&*store, "render")
+		.map_err(|_| Error::Generator("`render` must return an i32".into()))?;
+	let offset = match render
+		.call(&mut *store, (seed as i64, width as i32, height as i32))
+		.map_err(generator_error)?
+	{
+		0 => return Err(Error::Generator(format!("cannot render {}x{}", width, height))),
+		offset => offset as u32 as usize,
+	};
+
+	let memory = instance
+		.get_memory(&*store, "memory")
+		.ok_or_else(|| Error::Generator("the module does not export its memory".into()))?;
+
+	let mut rgba = vec![0; width as usize * height as usize * 4];
+	memory.read(&*store, offset, &mut rgba).map_err(generator_error)?;
+
+	Ok(rgba)
+}
+
+/// Render the pattern of `seed` on a `size` square canvas with the generator module `code`.
+pub fn render(code: &[u8], seed: u64, size: u32) -> Result<Pixmap, Error> {
+	from_rgba(size, size, &execute(code, seed, size, size)?)
+}
+
+/// Render the pattern of `seed` on a `size` square canvas with the generator module `code` as
+/// PNG bytes.
+pub fn render_png(code: &[u8], seed: u64, size: u32) -> Result<Vec<u8>, Error> {
+	render(code, seed, size)?.encode_png().map_err(|e| Error::Encoding(e.to_string()))
+}
+
+fn generator_error(e: impl fmt::Display) -> Error {
+	Error::Generator(e.to_string())
+}
@@ -0,0 +1,136 @@
+use patternities_render::{
+	render, render_as, render_png, render_svg, render_with, seed_from_key, Format, RenderParams,
+	CANVAS_SIZE,
+};
+use std::path::PathBuf;
+use tiny_skia::Pixmap;
+
+/// Largest difference allowed in the mean of a premultiplied channel between sizes of a pattern.
+const MEAN_TOLERANCE: f64 = 3.0;
+
+fn golden(seed: u64) -> Pixmap {
+	let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("../images")
+		.join(format!("{}-pattern.png", seed));
+	Pixmap::load_png(&path).expect("golden image exists")
+}
+
+fn mean_color(pixmap: &Pixmap) -> [f64; 4] {
+	let mut sum = [0.0; 4];
+	for pixel in pixmap.data().chunks_exact(4) {
+		for (sum, channel) in sum.iter_mut().zip(pixel) {
+			*sum += *channel as f64;
+		}
+	}
+	sum.map(|sum| sum / pixmap.pixels().len() as f64)
+}
+
+fn assert_matches_golden(seed: u64) {
+	let expected = golden(seed);
+	// PNG stores colours unpremultiplied, which the premultiplied pixmap does not survive at every
+	// anti-aliased edge, so compare the pattern as it is encoded.
+	let rendered = Pixmap::decode_png(&render_png(seed).unwrap()).unwrap();
+
+	assert_eq!((rendered.width(), rendered.height()), (expected.width(), expected.height()));
+
+	let differing = rendered.data().iter().zip(expected.data()).filter(|(a, b)| a != b).count();
+	assert_eq!(differing, 0, "seed {} differs in {} channels", seed, differing);
+}
+
+#[test]
+fn matches_golden_images() {
+	for seed in [2, 212, 2242, 9922, 13446, 224221131242] {
+		assert_matches_golden(seed);
+	}
+}
+
+#[test]
+fn rendering_is_deterministic() {
+	assert_eq!(render_png(9922).unwrap(), render_png(9922).unwrap());
+}
+
+#[test]
+fn png_has_canvas_size() {
+	let png = render_png(212).unwrap();
+	let decoded = Pixmap::decode_png(&png).unwrap();
+
+	assert_eq!((decoded.width(), decoded.height()), (CANVAS_SIZE, CANVAS_SIZE));
+}
+
+#[test]
+fn seed_is_taken_from_key() {
+	let mut key = [0u8; 16];
+	key[0] = 0x42;
+	key[8] = 0xff;
+
+	assert_eq!(seed_from_key(&key), 0x42);
+}
+
+#[test]
+fn svg_maps_the_pattern_onto_a_pattern_and_clip_path() {
+	let svg = render_svg(212);
+
+	assert!(svg.starts_with("<svg "));
+	assert!(svg.contains(r#"<pattern id="triangle" patternUnits="userSpaceOnUse""#));
+	assert!(svg.contains(r#"patternTransform="matrix(1.5 -0.4 0 -0.8 5 1)""#));
+	assert!(svg.contains(r#"<circle cx="200" cy="200" r="180"/>"#));
+	assert!(svg.contains(r#"fill="url(#triangle)" clip-path="url(#circle)""#));
+}
+
+#[test]
+fn renders_as_the_selected_format() {
+	let params = RenderParams::default();
+
+	assert_eq!(render_as(9922, Format::Png, &params).unwrap(), render_png(9922).unwrap());
+	assert_eq!(render_as(9922, Format::Svg, &params).unwrap(), render_svg(9922).into_bytes());
+	assert_eq!("svg".parse(), Ok(Format::Svg));
+	assert!("jpg".parse::<Format>().is_err());
+}
+
+#[test]
+fn default_params_render_today_s_pattern() {
+	assert_eq!(render_with(2242, &RenderParams::default()).unwrap(), render(2242).unwrap());
+	assert_eq!(
+		render_with(2242, &RenderParams::with_canvas_size(CANVAS_SIZE)).unwrap(),
+		render(2242).unwrap()
+	);
+}
+
+#[test]
+fn every_size_of_a_pattern_looks_the_same() {
+	let expected = mean_color(&render(9922).unwrap());
+
+	for size in [100, CANVAS_SIZE * 4] {
+		let rendered = render_with(9922, &RenderParams::with_canvas_size(size)).unwrap();
+		assert_eq!((rendered.width(), rendered.height()), (size, size));
+
+		for (channel, (a, b)) in mean_color(&rendered).iter().zip(expected).enumerate() {
+			assert!((a - b).abs() <= MEAN_TOLERANCE, "size {} channel {} is off", size, channel);
+		}
+	}
+}
+
+#[test]
+fn larger_tiles_render_the_same_pattern() {
+	let params = RenderParams { tile_size: 80, ..RenderParams::with_canvas_size(CANVAS_SIZE * 4) };
+	let rendered = render_with(212, &params).unwrap();
+
+	assert_eq!(rendered.width(), CANVAS_SIZE * 4);
+	let default_tile = render_with(212, &RenderParams::with_canvas_size(CANVAS_SIZE * 4)).unwrap();
+
+	// The tiles are laid out differently, but the colours of the pattern stay the same.
+	assert_ne!(rendered, default_tile);
+	let expected = mean_color(&default_tile);
+	for (channel, (a, b)) in mean_color(&rendered).iter().zip(expected).enumerate() {
+		assert!((a - b).abs() <= MEAN_TOLERANCE, "channel {} is off", channel);
+	}
+}
+
+#[test]
+fn svg_scales_to_the_canvas() {
+	let params = RenderParams { anti_alias: false, ..RenderParams::with_canvas_size(3840) };
+	let svg = String::from_utf8(render_as(212, Format::Svg, &params).unwrap()).unwrap();
+
+	assert!(svg.contains(r#"width="3840" height="3840" viewBox="0 0 400 400""#));
+	assert!(svg.contains(r#"shape-rendering="crispEdges""#));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "wasm")]
+
+use patternities_render::{wasm, Error, CANVAS_SIZE};
+
+/// A module importing `env.now`, which generators have no way to reach.
+const IMPORTING_MODULE: &[u8] = &[
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+	0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: () -> ()
+	0x02, 0x0b, 0x01, 0x03, b'e', b'n', b'v', 0x03, b'n', b'o', b'w', 0x00, 0x00, // import
+];
+
+/// A module whose `render` loops forever.
+const LOOPING_MODULE: &[u8] = &[
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+	0x01, 0x08, 0x01, 0x60, 0x03, 0x7e, 0x7f, 0x7f, 0x01, 0x7f, // type section: render
+	0x03, 0x02, 0x01, 0x00, // function section
+	0x05, 0x03, 0x01, 0x00, 0x01, // memory section: 1 page, no maximum
+	0x07, 0x13, 0x02, // export section
+	0x06, b'r', b'e', b'n', b'd', b'e', b'r', 0x00, 0x00, // render
+	0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, // memory
+	0x0a, 0x0b, 0x01, 0x09, 0x00, // code section
+	0x03, 0x40, 0x0c, 0x00, 0x0b, // loop br 0 end
+	0x41, 0x00, 0x0b, // i32.const 0 end
+];
+
+/// A module whose `render` grows its memory past `MAX_MEMORY_PAGES`, returning 1 if that failed
+/// and 0 if it did not.
+const GROWING_MODULE: &[u8] = &[
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+	0x01, 0x08, 0x01, 0x60, 0x03, 0x7e, 0x7f, 0x7f, 0x01, 0x7f, // type section: render
+	0x03, 0x02, 0x01, 0x00, // function section
+	0x05, 0x03, 0x01, 0x00, 0x01, // memory section: 1 page, no maximum
+	0x07, 0x13, 0x02, // export section
+	0x06, b'r', b'e', b'n', b'd', b'e', b'r', 0x00, 0x00, // render
+	0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, // memory
+	0x0a, 0x0c, 0x01, 0x0a, 0x00, // code section
+	0x41, 0x80, 0x20, 0x40, 0x00, // memory.grow (i32.const 4096)
+	0x41, 0x7f, 0x46, 0x0b, // i32.eq (i32.const -1) end
+];
+
+#[test]
+fn gives_up_on_generators_that_do_not_finish() {
+	let result = wasm::execute_with_fuel(LOOPING_MODULE, 2, 1, 1, 1_000);
+	assert!(matches!(result, Err(Error::Generator(_))));
+	assert!(matches!(wasm::execute(LOOPING_MODULE, 2, 1, 1), Err(Error::Generator(_))));
+}
+
+#[test]
+fn caps_generator_memory() {
+	assert_eq!(wasm::MAX_MEMORY_PAGES, 4096);
+	assert_eq!(wasm::execute(GROWING_MODULE, 2, 1, 1).unwrap().len(), 4);
+}
+
+#[test]
+fn rejects_modules_with_imports() {
+	assert!(matches!(
+		wasm::execute(IMPORTING_MODULE, 2, CANVAS_SIZE, CANVAS_SIZE),
+		Err(Error::Generator(_))
+	));
+}
+
+#[test]
+fn rejects_invalid_modules() {
+	assert!(matches!(wasm::render(b"not wasm", 2, CANVAS_SIZE), Err(Error::Generator(_))));
+}
+
+#[test]
+fn rejects_oversized_canvas() {
+	let size = wasm::MAX_CANVAS_SIZE + 1;
+	assert!(matches!(wasm::execute(IMPORTING_MODULE, 2, size, size), Err(Error::Canvas)));
+}
@@ -14,7 +14,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -31,7 +34,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -96,12 +99,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	//   the compatible custom types. Bump it with every runtime upgrade, or `set_code` rejects it.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -235,6 +238,9 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The minimum balance an account needs to exist.
+pub const EXISTENTIAL_DEPOSIT: Balance = 500;
+
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ();
@@ -244,7 +250,7 @@ impl pallet_balances::Config for Runtime {
 	/// The ubiquitous event type.
 	type Event = Event;
 	type DustRemoval = ();
-	type ExistentialDeposit = ConstU128<500>;
+	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
@@ -269,15 +275,41 @@ impl pallet_sudo::Config for Runtime {
 
 parameter_types! {             
     pub const MaxPatternityOwned: u32 = 9999;
-	pub const MaxByteCipher: u32 = 10000;
+	pub const MaxProgramLen: u32 = 10000;
+	pub const MaxOffers: u32 = 64;
+	pub const MaxAuctionsEnding: u32 = 100;
+	pub const ResealPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRoyalty: Permill = Permill::from_percent(25);
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const PatternitiesPalletId: PalletId = PalletId(*b"py/pattn");
+	/// Marketplace fees are collected in an account derived from the pallet id.
+	pub PatternitiesFeeDestination: AccountId = PatternitiesPalletId::get().into_account();
+	pub const MaxCollectionNameLen: u32 = 64;
+	pub const PatternStringLimit: u32 = 256;
+	pub const MaxPatternAttributes: u32 = 16;
+	pub const MetadataDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT / 10;
 }
 
 /// Configure the pallet-the-patternities in pallets/the-patternities.
 impl pallet_the_patternities::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type Randomness = RandomnessCollectiveFlip;
 	type MaxPatternityOwned = MaxPatternityOwned;
-	type MaxByteCipher = MaxByteCipher;
+	type MaxProgramLen = MaxProgramLen;
+	type MaxOffers = MaxOffers;
+	type MaxAuctionsEnding = MaxAuctionsEnding;
+	type ResealPeriod = ResealPeriod;
+	type MaxRoyalty = MaxRoyalty;
+	type MarketplaceFee = MarketplaceFee;
+	type FeeDestination = PatternitiesFeeDestination;
+	type MaxCollectionNameLen = MaxCollectionNameLen;
+	type StringLimit = PatternStringLimit;
+	type MaxAttributes = MaxPatternAttributes;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = pallet_the_patternities::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -339,7 +371,7 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
-		[pallet_template, TemplateModule]
+		[pallet_the_patternities, ThePatternities]
 	);
 }
 
@@ -459,6 +491,68 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_the_patternities_rpc_runtime_api::PatternitiesApi<Block, AccountId, Balance, Hash> for Runtime {
+		fn pattern(
+			pattern_id: Hash,
+		) -> Option<
+			pallet_the_patternities_rpc_runtime_api::PatternInfo<AccountId, Balance, Hash>,
+		> {
+			ThePatternities::pattern_info(&pattern_id)
+		}
+
+		fn pattern_owner(pattern_id: Hash) -> Option<AccountId> {
+			ThePatternities::patternity(&pattern_id).map(|pattern| pattern.owner)
+		}
+
+		fn pattern_price(pattern_id: Hash) -> Option<Balance> {
+			ThePatternities::patternity(&pattern_id).and_then(|pattern| pattern.price)
+		}
+
+		fn key_status(
+			pattern_id: Hash,
+		) -> Option<pallet_the_patternities_rpc_runtime_api::KeyStatus<AccountId>> {
+			ThePatternities::key_status(&pattern_id)
+		}
+
+		fn patterns_owned(owner: AccountId, start: u32, limit: u32) -> Vec<Hash> {
+			ThePatternities::patterns_owned_page(&owner, start, limit)
+		}
+
+		fn patterns_for_sale(start: u32, limit: u32) -> Vec<(Hash, Balance)> {
+			ThePatternities::patterns_for_sale(start, limit)
+		}
+
+		fn pattern_count() -> u64 {
+			ThePatternities::patternity_cnt()
+		}
+
+		fn patterns_in_collection(
+			collection_id: pallet_the_patternities_rpc_runtime_api::CollectionId,
+			start: u32,
+			limit: u32,
+		) -> Vec<Hash> {
+			ThePatternities::patterns_in_collection(collection_id, start, limit)
+		}
+
+		fn pattern_traits(
+			pattern_id: Hash,
+		) -> Option<pallet_the_patternities_rpc_runtime_api::PatternTraits> {
+			ThePatternities::pattern_traits(&pattern_id)
+		}
+
+		fn traits_of_seed(seed: u64) -> pallet_the_patternities_rpc_runtime_api::PatternTraits {
+			ThePatternities::traits_of_seed(seed)
+		}
+
+		fn rarity_score(pattern_id: Hash) -> Option<u64> {
+			ThePatternities::rarity_score(&pattern_id)
+		}
+
+		fn generator_program(program: Hash) -> Option<Vec<u8>> {
+			ThePatternities::generator_program(program).map(|code| code.into_inner())
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -525,3 +619,164 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pallet_the_patternities_rpc_runtime_api::{
+		runtime_decl_for_PatternitiesApi::PatternitiesApi, KeyStatus,
+	};
+	use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+	use sp_keyring::AccountKeyring;
+	use sp_runtime::{
+		generic::{Digest, DigestItem, Era},
+		traits::Header as _,
+	};
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		GenesisConfig {
+			balances: BalancesConfig {
+				balances: vec![(AccountKeyring::Alice.to_account_id(), 1 << 60)],
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap()
+		.into()
+	}
+
+	fn signed(call: Call, nonce: Index) -> UncheckedExtrinsic {
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::Immortal),
+			frame_system::CheckNonce::from(nonce),
+			frame_system::CheckWeight::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::from(0),
+		);
+		let payload = generic::SignedPayload::new(call, extra).unwrap();
+		let signature = payload.using_encoded(|payload| AccountKeyring::Alice.sign(payload));
+		let (call, extra, _) = payload.deconstruct();
+
+		UncheckedExtrinsic::new_signed(
+			call,
+			AccountKeyring::Alice.to_account_id().into(),
+			signature.into(),
+			extra,
+		)
+	}
+
+	/// Authors block `number` in slot `number` with the given extrinsics, as a node importing it
+	/// would execute it, and returns the finalized header.
+	fn produce_block(
+		number: BlockNumber,
+		parent_hash: Hash,
+		extrinsics: Vec<UncheckedExtrinsic>,
+	) -> Header {
+		let slot = Slot::from(number as u64);
+		let digest = Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())] };
+		Executive::initialize_block(&Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			parent_hash,
+			digest,
+		));
+
+		let now = TimestampCall::set { now: *slot * SLOT_DURATION };
+		assert_eq!(
+			Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(Call::Timestamp(now))),
+			Ok(Ok(()))
+		);
+		for extrinsic in extrinsics {
+			assert_eq!(Executive::apply_extrinsic(extrinsic), Ok(Ok(())));
+		}
+
+		Executive::finalize_block()
+	}
+
+	#[test]
+	fn minting_is_deterministic_across_nodes() {
+		// Every node executing the same blocks must reach the same state, including the seed salt
+		// drawn in the block after the mint.
+		let import = || {
+			new_test_ext().execute_with(|| {
+				let (key, iv) = ([1; 16], [2; 8]);
+				let call = Call::ThePatternities(pallet_the_patternities::Call::create_pattern {
+					price: None,
+					key_hash: ThePatternities::key_hash(&key, &iv),
+					sealed_key: [7; pallet_the_patternities::SEALED_KEY_LEN],
+					royalty: Permill::zero(),
+					generator: None,
+				});
+
+				let first = produce_block(1, Default::default(), vec![signed(call, 0)]);
+				let second = produce_block(2, first.hash(), vec![]);
+
+				let owned = Runtime::patterns_owned(AccountKeyring::Alice.to_account_id(), 0, 10);
+				assert_eq!(owned.len(), 1);
+				assert!(Runtime::pattern(owned[0]).unwrap().seed_salt.is_some());
+
+				(first, second)
+			})
+		};
+
+		let (first, second) = import();
+		let (other_first, other_second) = import();
+		assert_eq!(first.state_root(), other_first.state_root());
+		assert_eq!(second.state_root(), other_second.state_root());
+		assert_eq!(second, other_second);
+	}
+
+	#[test]
+	fn patternities_api_serves_minted_pattern() {
+		new_test_ext().execute_with(|| {
+			let alice = AccountKeyring::Alice.to_account_id();
+			let (key, iv) = ([1; 16], [2; 8]);
+
+			Executive::initialize_block(&Header::new(
+				1,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			));
+
+			let call = Call::ThePatternities(pallet_the_patternities::Call::create_pattern {
+				price: Some(1_000),
+				key_hash: ThePatternities::key_hash(&key, &iv),
+				sealed_key: [7; pallet_the_patternities::SEALED_KEY_LEN],
+				royalty: Permill::from_percent(5),
+				generator: None,
+			});
+			assert_eq!(Executive::apply_extrinsic(signed(call, 0)), Ok(Ok(())));
+
+			assert_eq!(Runtime::pattern_count(), 1);
+
+			let owned = Runtime::patterns_owned(alice.clone(), 0, 10);
+			assert_eq!(owned.len(), 1);
+			let pattern_id = owned[0];
+
+			let info = Runtime::pattern(pattern_id).unwrap();
+			assert_eq!(info.owner, alice);
+			assert_eq!(info.price, Some(1_000));
+			assert_eq!(info.key_hash, ThePatternities::key_hash(&key, &iv));
+			assert_eq!(info.program, ThePatternities::builtin_program());
+			assert_eq!(
+				Runtime::generator_program(info.program),
+				Some(pallet_the_patternities::BUILTIN_PROGRAM.as_bytes().to_vec())
+			);
+			assert_eq!(info.key_status, KeyStatus::Sealed);
+			assert_eq!(info.creator, alice);
+			assert_eq!(info.royalty, Permill::from_percent(5));
+
+			assert_eq!(Runtime::pattern_owner(pattern_id), Some(alice));
+			assert_eq!(Runtime::pattern_price(pattern_id), Some(1_000));
+			assert_eq!(Runtime::patterns_for_sale(0, 10), vec![(pattern_id, 1_000)]);
+			assert_eq!(Runtime::pattern(Hash::repeat_byte(1)), None);
+		});
+	}
+}